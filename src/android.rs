@@ -0,0 +1,295 @@
+//! Native bridge into Android's `PowerManager` and `NotificationManager`,
+//! used to keep a long Ollama completion streaming - and visible - while the
+//! WebView is backgrounded. Only compiled into the native server binary
+//! behind the `android` feature: this needs a JNI context to call through,
+//! which the wasm32 frontend never has (see `GenerationGuard` usage in
+//! `main.rs`'s `stream_handler`).
+//!
+//! This is a wake-lock-and-notification bridge, not a foreground-service
+//! one - there's no `Service` class or `AndroidManifest.xml` in this tree to
+//! register (see `GenerationGuard`'s doc comment for what that gap means in
+//! practice).
+//!
+//! Built on `ndk-context` for the `JavaVM`/`Activity` handles the NDK
+//! exposes, and the `jni` crate for the actual `PowerManager`/
+//! `NotificationManager` method calls - `ndk`/`ndk-sys` alone don't wrap
+//! those framework services, only the lower-level NativeActivity surface.
+#![cfg(feature = "android")]
+
+use jni::objects::{GlobalRef, JObject, JValue};
+use jni::JavaVM;
+use std::sync::OnceLock;
+
+const WAKE_LOCK_TAG: &str = "OllamaRust:Generation";
+const NOTIFICATION_CHANNEL_ID: &str = "ollama_generation";
+const NOTIFICATION_CHANNEL_NAME: &str = "Ollama generation";
+const NOTIFICATION_ID: i32 = 1;
+/// Matches the `<receiver>` the host Android app registers for the
+/// notification's cancel action; this crate only broadcasts the intent.
+const CANCEL_ACTION: &str = "ollama_rust.action.CANCEL_GENERATION";
+/// Safety cap so a leaked guard can't hold the device awake forever if a
+/// stream is dropped without running its destructor (process killed, etc).
+const WAKE_LOCK_TIMEOUT_MS: i64 = 10 * 60 * 1000;
+
+#[derive(Debug)]
+pub struct AndroidBridgeError(String);
+
+impl std::fmt::Display for AndroidBridgeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for AndroidBridgeError {}
+
+impl From<jni::errors::Error> for AndroidBridgeError {
+    fn from(e: jni::errors::Error) -> Self {
+        AndroidBridgeError(e.to_string())
+    }
+}
+
+fn java_vm() -> &'static JavaVM {
+    static VM: OnceLock<JavaVM> = OnceLock::new();
+    VM.get_or_init(|| {
+        let ctx = ndk_context::android_context();
+        unsafe { JavaVM::from_raw(ctx.vm().cast()) }.expect("not running inside an Android process")
+    })
+}
+
+// `android_context()` hands back a raw pointer valid for the life of the
+// process, so it's fine to hand this out with an unbound lifetime rather
+// than threading a real one through every call in this module.
+fn activity() -> JObject<'static> {
+    let ctx = ndk_context::android_context();
+    unsafe { JObject::from_raw(ctx.context().cast()) }
+}
+
+/// Registers `NOTIFICATION_CHANNEL_ID` with the system, if it hasn't been
+/// already. Required on API 26+ - `NotificationManager.notify` silently
+/// drops any notification posted against a channel that was never created,
+/// so this has to run at least once before the first `notify()` call.
+/// Re-creating an already-registered channel with the same importance is a
+/// no-op per the platform docs, so callers don't need to track this
+/// themselves.
+fn ensure_notification_channel(env: &mut jni::JNIEnv, manager: &JObject) -> Result<(), AndroidBridgeError> {
+    static CHANNEL_CREATED: OnceLock<()> = OnceLock::new();
+    if CHANNEL_CREATED.get().is_some() {
+        return Ok(());
+    }
+
+    let channel_id = env.new_string(NOTIFICATION_CHANNEL_ID)?;
+    let channel_name = env.new_string(NOTIFICATION_CHANNEL_NAME)?;
+    let channel_class = env.find_class("android/app/NotificationChannel")?;
+    let channel = env.new_object(
+        channel_class,
+        "(Ljava/lang/String;Ljava/lang/CharSequence;I)V",
+        // IMPORTANCE_DEFAULT - shows in the shade without an intrusive sound,
+        // which matches an ongoing "still generating" notification.
+        &[JValue::Object(&channel_id), JValue::Object(&channel_name), JValue::Int(3)],
+    )?;
+    env.call_method(
+        manager,
+        "createNotificationChannel",
+        "(Landroid/app/NotificationChannel;)V",
+        &[JValue::Object(&channel)],
+    )?;
+
+    let _ = CHANNEL_CREATED.set(());
+    Ok(())
+}
+
+/// Posts (or updates, if `tokens > 0`) the ongoing "generating" notification
+/// for `model`, wired to cancel `CANCEL_ACTION` from the shade.
+fn notify(env: &mut jni::JNIEnv, activity: &JObject, model: &str, tokens: u32) -> Result<(), AndroidBridgeError> {
+    let notification_service = env.new_string("notification")?;
+    let manager = env
+        .call_method(
+            activity,
+            "getSystemService",
+            "(Ljava/lang/String;)Ljava/lang/Object;",
+            &[JValue::Object(&notification_service)],
+        )?
+        .l()?;
+    ensure_notification_channel(env, &manager)?;
+
+    let channel_id = env.new_string(NOTIFICATION_CHANNEL_ID)?;
+    let title = env.new_string("Ollama Rust")?;
+    let text = env.new_string(if tokens == 0 {
+        format!("Generating with {model}...")
+    } else {
+        format!("Generating with {model} - {tokens} tokens so far")
+    })?;
+
+    let cancel_action = env.new_string(CANCEL_ACTION)?;
+    let intent_class = env.find_class("android/content/Intent")?;
+    let intent = env.new_object(intent_class, "(Ljava/lang/String;)V", &[JValue::Object(&cancel_action)])?;
+    let pending_intent = env
+        .call_static_method(
+            "android/app/PendingIntent",
+            "getBroadcast",
+            "(Landroid/content/Context;ILandroid/content/Intent;I)Landroid/app/PendingIntent;",
+            &[
+                JValue::Object(activity),
+                JValue::Int(NOTIFICATION_ID),
+                JValue::Object(&intent),
+                // FLAG_IMMUTABLE | FLAG_UPDATE_CURRENT
+                JValue::Int(0x04000000 | 0x08000000),
+            ],
+        )?
+        .l()?;
+
+    let builder_class = env.find_class("android/app/Notification$Builder")?;
+    let builder = env.new_object(
+        builder_class,
+        "(Landroid/content/Context;Ljava/lang/String;)V",
+        &[JValue::Object(activity), JValue::Object(&channel_id)],
+    )?;
+    env.call_method(&builder, "setContentTitle", "(Ljava/lang/CharSequence;)Landroid/app/Notification$Builder;", &[JValue::Object(&title)])?;
+    env.call_method(&builder, "setContentText", "(Ljava/lang/CharSequence;)Landroid/app/Notification$Builder;", &[JValue::Object(&text)])?;
+    env.call_method(&builder, "setOngoing", "(Z)Landroid/app/Notification$Builder;", &[JValue::Bool(1)])?;
+    env.call_method(
+        &builder,
+        "setDeleteIntent",
+        "(Landroid/app/PendingIntent;)Landroid/app/Notification$Builder;",
+        &[JValue::Object(&pending_intent)],
+    )?;
+    let notification = env.call_method(&builder, "build", "()Landroid/app/Notification;", &[])?.l()?;
+
+    env.call_method(
+        &manager,
+        "notify",
+        "(ILandroid/app/Notification;)V",
+        &[JValue::Int(NOTIFICATION_ID), JValue::Object(&notification)],
+    )?;
+
+    Ok(())
+}
+
+fn cancel_notification(env: &mut jni::JNIEnv, activity: &JObject) -> Result<(), AndroidBridgeError> {
+    let notification_service = env.new_string("notification")?;
+    let manager = env
+        .call_method(
+            activity,
+            "getSystemService",
+            "(Ljava/lang/String;)Ljava/lang/Object;",
+            &[JValue::Object(&notification_service)],
+        )?
+        .l()?;
+    env.call_method(&manager, "cancel", "(I)V", &[JValue::Int(NOTIFICATION_ID)])?;
+    Ok(())
+}
+
+/// Holds `PowerManager.PARTIAL_WAKE_LOCK` for the life of a generation.
+///
+/// This is a wake-lock plus an ongoing notification, not a real Android
+/// foreground service: there's no `Service` component or manifest entry in
+/// this tree to host one (this crate ships only the native Rust/JNI side).
+/// That means the OS can still kill the process under memory pressure the
+/// way it wouldn't for a `startForegroundService` app - the wake-lock keeps
+/// the CPU awake and the notification keeps the user informed, but neither
+/// grants the stronger lifecycle protection a foreground service would.
+struct PartialWakeLock(GlobalRef);
+
+impl PartialWakeLock {
+    fn acquire(env: &mut jni::JNIEnv, activity: &JObject) -> Result<Self, AndroidBridgeError> {
+        let power_service = env.new_string("power")?;
+        let power_manager = env
+            .call_method(
+                activity,
+                "getSystemService",
+                "(Ljava/lang/String;)Ljava/lang/Object;",
+                &[JValue::Object(&power_service)],
+            )?
+            .l()?;
+
+        let tag = env.new_string(WAKE_LOCK_TAG)?;
+        // PowerManager.PARTIAL_WAKE_LOCK
+        let wake_lock = env
+            .call_method(
+                &power_manager,
+                "newWakeLock",
+                "(ILjava/lang/String;)Landroid/os/PowerManager$WakeLock;",
+                &[JValue::Int(1), JValue::Object(&tag)],
+            )?
+            .l()?;
+        env.call_method(&wake_lock, "acquire", "(J)V", &[JValue::Long(WAKE_LOCK_TIMEOUT_MS)])?;
+
+        Ok(Self(env.new_global_ref(wake_lock)?))
+    }
+
+    fn release(&self, env: &mut jni::JNIEnv) {
+        if let Err(e) = env.call_method(self.0.as_obj(), "release", "()V", &[]) {
+            eprintln!("android: failed to release wake-lock: {e}");
+        }
+    }
+}
+
+/// Keeps the device awake and shows a cancellable ongoing notification for
+/// the life of one streamed generation. Start it when a stream begins and
+/// let it drop when the request future completes or is cancelled - the
+/// teardown happens in `Drop`, so it survives the WebView being
+/// backgrounded and can't be left dangling by an early return.
+///
+/// See the note on `PartialWakeLock` above: this is not a foreground
+/// service, so it doesn't protect generation from being killed outright
+/// under memory pressure, only from the CPU sleeping and from the user
+/// losing track of it.
+pub struct GenerationGuard {
+    model: String,
+    wake_lock: Option<PartialWakeLock>,
+}
+
+impl GenerationGuard {
+    /// Acquires the wake-lock and posts the initial notification. A JNI
+    /// failure here is logged and swallowed rather than failing the chat
+    /// request - a missing notification shouldn't break generation.
+    pub fn start(model: &str) -> Self {
+        match Self::try_start(model) {
+            Ok(guard) => guard,
+            Err(e) => {
+                eprintln!("android: failed to start generation service: {e}");
+                GenerationGuard { model: model.to_string(), wake_lock: None }
+            }
+        }
+    }
+
+    fn try_start(model: &str) -> Result<Self, AndroidBridgeError> {
+        let vm = java_vm();
+        let mut env = vm.attach_current_thread()?;
+        let activity = activity();
+        let wake_lock = PartialWakeLock::acquire(&mut env, &activity)?;
+        notify(&mut env, &activity, model, 0)?;
+        Ok(GenerationGuard { model: model.to_string(), wake_lock: Some(wake_lock) })
+    }
+
+    /// Updates the ongoing notification with tokens streamed so far. Call
+    /// this periodically (not per-token) to avoid flooding the notification
+    /// manager during a fast generation.
+    pub fn update_progress(&self, tokens: u32) {
+        if self.wake_lock.is_none() {
+            return;
+        }
+        let result = (|| -> Result<(), AndroidBridgeError> {
+            let vm = java_vm();
+            let mut env = vm.attach_current_thread()?;
+            let activity = activity();
+            notify(&mut env, &activity, &self.model, tokens)
+        })();
+        if let Err(e) = result {
+            eprintln!("android: failed to update generation notification: {e}");
+        }
+    }
+}
+
+impl Drop for GenerationGuard {
+    fn drop(&mut self) {
+        let Some(wake_lock) = self.wake_lock.take() else { return };
+        let vm = java_vm();
+        let Ok(mut env) = vm.attach_current_thread() else { return };
+        wake_lock.release(&mut env);
+        let activity = activity();
+        if let Err(e) = cancel_notification(&mut env, &activity) {
+            eprintln!("android: failed to cancel generation notification: {e}");
+        }
+    }
+}
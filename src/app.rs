@@ -1,6 +1,11 @@
 use leptos::prelude::*;
 use leptos::task::spawn_local;
-use leptos_meta::{provide_meta_context, MetaTags, Stylesheet, Title};
+use leptos_meta::{provide_meta_context, Meta, MetaTags, Stylesheet, Title};
+use leptos_router::components::{Route, Router, Routes};
+use leptos_router::hooks::{use_navigate, use_params_map};
+use leptos_router::path;
+use crate::error_template::{ErrorTemplate, OllamaRequestError};
+use std::error::Error as _;
 use pulldown_cmark::{Parser, Options, html};
 use serde::{Deserialize, Serialize};
 
@@ -17,6 +22,157 @@ fn markdown_to_html(text: &str) -> String {
     html_output
 }
 
+/// Strip markdown syntax so read-aloud doesn't speak asterisks, backticks,
+/// or code fences - code blocks are dropped entirely rather than read out.
+fn strip_markdown_for_speech(text: &str) -> String {
+    let mut result = String::new();
+    let mut in_code_block = false;
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+        let mut line = line.replace('`', "");
+        for marker in ["**", "__", "~~"] {
+            line = line.replace(marker, "");
+        }
+        let line = line.trim_start_matches(['#', '*', '-', ' ']);
+        result.push_str(line);
+        result.push(' ');
+    }
+    result.trim().to_string()
+}
+
+/// Escape raw text for safe use with `inner_html`.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Wrap case-insensitive occurrences of `query` in `<mark>` tags, skipping
+/// anything inside an HTML tag so this is safe to run on already-rendered
+/// markdown output as well as escaped plain text.
+fn highlight_html(html: &str, query: &str) -> String {
+    if query.is_empty() {
+        return html.to_string();
+    }
+    let chars: Vec<char> = html.chars().collect();
+    let lower_chars: Vec<char> = html.to_lowercase().chars().collect();
+    let query_lower: Vec<char> = query.to_lowercase().chars().collect();
+    let qlen = query_lower.len();
+
+    let mut result = String::with_capacity(html.len());
+    let mut in_tag = false;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '<' {
+            in_tag = true;
+            result.push(c);
+            i += 1;
+            continue;
+        }
+        if c == '>' {
+            in_tag = false;
+            result.push(c);
+            i += 1;
+            continue;
+        }
+        if !in_tag && i + qlen <= lower_chars.len() && lower_chars[i..i + qlen] == query_lower[..] {
+            let matched: String = chars[i..i + qlen].iter().collect();
+            result.push_str("<mark class=\"search-hit\">");
+            result.push_str(&matched);
+            result.push_str("</mark>");
+            i += qlen;
+            continue;
+        }
+        result.push(c);
+        i += 1;
+    }
+    result
+}
+
+/// Hex colors for the "Custom" theme, applied as inline `--*` CSS variable
+/// overrides on the app root rather than a `data-theme` class.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CustomThemeColors {
+    pub background: String,
+    pub surface: String,
+    pub accent: String,
+    pub text: String,
+    pub user_bubble: String,
+    pub ai_bubble: String,
+}
+
+impl Default for CustomThemeColors {
+    fn default() -> Self {
+        preset_theme_colors("dark")
+    }
+}
+
+/// The CSS variable name each `CustomThemeColors` field maps to.
+const CUSTOM_THEME_VARS: &[(&str, fn(&CustomThemeColors) -> &str)] = &[
+    ("--bg-color", |c| &c.background),
+    ("--surface-color", |c| &c.surface),
+    ("--accent-color", |c| &c.accent),
+    ("--text-color", |c| &c.text),
+    ("--user-bubble-color", |c| &c.user_bubble),
+    ("--ai-bubble-color", |c| &c.ai_bubble),
+];
+
+/// A starting palette for the "Custom" picker, seeded from whichever preset
+/// theme was active - lets users start from e.g. Nordic and tweak from there.
+fn preset_theme_colors(theme: &str) -> CustomThemeColors {
+    match theme {
+        "dark" => CustomThemeColors {
+            background: "#1e1e1e".to_string(),
+            surface: "#2a2a2a".to_string(),
+            accent: "#4a9eff".to_string(),
+            text: "#e8e8e8".to_string(),
+            user_bubble: "#2d5a8c".to_string(),
+            ai_bubble: "#333333".to_string(),
+        },
+        "amoled" => CustomThemeColors {
+            background: "#000000".to_string(),
+            surface: "#0a0a0a".to_string(),
+            accent: "#4a9eff".to_string(),
+            text: "#f0f0f0".to_string(),
+            user_bubble: "#1a3a5c".to_string(),
+            ai_bubble: "#111111".to_string(),
+        },
+        "hacker" => CustomThemeColors {
+            background: "#0d0d0d".to_string(),
+            surface: "#111111".to_string(),
+            accent: "#00ff41".to_string(),
+            text: "#00ff41".to_string(),
+            user_bubble: "#003b00".to_string(),
+            ai_bubble: "#001a00".to_string(),
+        },
+        "nordic" => CustomThemeColors {
+            background: "#2e3440".to_string(),
+            surface: "#3b4252".to_string(),
+            accent: "#88c0d0".to_string(),
+            text: "#eceff4".to_string(),
+            user_bubble: "#5e81ac".to_string(),
+            ai_bubble: "#434c5e".to_string(),
+        },
+        _ => CustomThemeColors {
+            background: "#ffffff".to_string(),
+            surface: "#f5f5f5".to_string(),
+            accent: "#2d7ff9".to_string(),
+            text: "#1a1a1a".to_string(),
+            user_bubble: "#2d7ff9".to_string(),
+            ai_bubble: "#eaeaea".to_string(),
+        },
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct StatusResponse {
     pub running: bool,
@@ -28,6 +184,10 @@ pub struct CloudLoginResponse {
     pub success: bool,
     pub message: String,
     pub api_key: Option<String>,
+    /// Set when `message` is an "identity already exists" rejection - names
+    /// the provider the email is already linked to, so the client can offer
+    /// to link instead of just failing.
+    pub existing_provider: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -42,34 +202,255 @@ pub struct CloudModelsResponse {
     pub models: Vec<CloudModel>,
 }
 
+/// A model-picker search query parsed GitLab-filtered-search style: zero or
+/// more `key:value` tokens (`name:`, `family:`, `size:`, `quant:`) plus
+/// leftover free text, matched against `display_name`/`description`.
+#[derive(Default, Clone, Debug)]
+pub struct ModelFilterQuery {
+    pub name: Option<String>,
+    pub family: Option<String>,
+    pub size: Option<String>,
+    pub quant: Option<String>,
+    pub free_text: Vec<String>,
+}
+
+pub fn parse_model_filter_query(input: &str) -> ModelFilterQuery {
+    let mut query = ModelFilterQuery::default();
+    for token in input.split_whitespace() {
+        if let Some(value) = token.strip_prefix("name:") {
+            query.name = Some(value.to_lowercase());
+        } else if let Some(value) = token.strip_prefix("family:") {
+            query.family = Some(value.to_lowercase());
+        } else if let Some(value) = token.strip_prefix("size:") {
+            query.size = Some(value.to_lowercase());
+        } else if let Some(value) = token.strip_prefix("quant:") {
+            query.quant = Some(value.to_lowercase());
+        } else {
+            query.free_text.push(token.to_lowercase());
+        }
+    }
+    query
+}
+
+/// Splits an Ollama-style model id (`llama3.1:70b-instruct-q4_0`) into a
+/// `(family, size, quant)` guess, used to answer `family:`/`size:`/`quant:`
+/// filter tokens without a real model-metadata source.
+fn parse_model_identity(model_id: &str) -> (String, Option<String>, Option<String>) {
+    let (family, tag) = match model_id.split_once(':') {
+        Some((family, tag)) => (family.to_lowercase(), tag.to_lowercase()),
+        None => (model_id.to_lowercase(), String::new()),
+    };
+    let mut size = None;
+    let mut quant = None;
+    for part in tag.split('-') {
+        if part.starts_with('q') && part.len() > 1 && part[1..].chars().next().is_some_and(|c| c.is_ascii_digit()) {
+            quant = Some(part.to_string());
+        } else if part.ends_with('b') || part.ends_with('m') {
+            let digits = &part[..part.len() - 1];
+            if !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit() || c == '.') {
+                size = Some(part.to_string());
+            }
+        }
+    }
+    (family, size, quant)
+}
+
+/// Whether a model satisfies a parsed filter query - every `key:value` token
+/// must match, and every free-text token must substring-match either the
+/// display name or description.
+pub fn model_matches_filter(model_id: &str, display_name: &str, description: &str, query: &ModelFilterQuery) -> bool {
+    let (family, size, quant) = parse_model_identity(model_id);
+
+    if let Some(name) = &query.name {
+        if !model_id.to_lowercase().contains(name) && !display_name.to_lowercase().contains(name) {
+            return false;
+        }
+    }
+    if let Some(wanted) = &query.family {
+        if !family.contains(wanted) {
+            return false;
+        }
+    }
+    if let Some(wanted) = &query.size {
+        if size.as_deref() != Some(wanted.as_str()) {
+            return false;
+        }
+    }
+    if let Some(wanted) = &query.quant {
+        if quant.as_deref() != Some(wanted.as_str()) {
+            return false;
+        }
+    }
+
+    let haystack = format!("{} {}", display_name.to_lowercase(), description.to_lowercase());
+    query.free_text.iter().all(|token| haystack.contains(token))
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct ChatMessage {
+    /// Monotonic, assigned once at creation - keyed on directly by the `<For>`
+    /// loop so a streaming AI bubble stays the same DOM node while its text
+    /// fills in, instead of one keyed off the text itself.
+    #[serde(default)]
+    pub id: u64,
     pub role: String,
     pub text: String,
+    /// Base64 (data-URL) image attachments sent alongside this turn, for
+    /// vision-capable models.
+    #[serde(default)]
+    pub images: Vec<String>,
+    /// Emoji reactions on this message, keyed by emoji with a tally - a quick
+    /// feedback channel (flagging good/bad responses) that exports alongside
+    /// the rest of the chat history instead of needing a separate rating UI.
+    #[serde(default)]
+    pub reactions: HashMap<String, u32>,
 }
 
+/// Palette offered by the reaction picker - kept short and generic rather
+/// than modeling every possible emoji.
+const REACTION_PALETTE: &[&str] = &["👍", "👎", "❤️", "😂", "🎉", "😮", "😕", "🔁"];
+
+/// Snapshot of an in-flight completion, persisted so an Android WebView
+/// reload or process kill mid-stream doesn't just lose the response.
+/// Ollama's API has no resume token, so this doesn't reconnect to the same
+/// generation - it's enough to re-issue the same request and reconcile the
+/// new tokens against `partial_text` already shown. Cleared as soon as the
+/// stream it describes finishes (successfully or not).
 #[derive(Serialize, Deserialize, Clone, Debug)]
-pub struct BraveSearchResult {
+struct ResumableGeneration {
+    message_id: u64,
+    model: String,
+    history: Vec<(String, String)>,
+    partial_text: String,
+}
+
+const RESUMABLE_GENERATION_KEY: &str = "resumable_generation";
+
+#[cfg(target_arch = "wasm32")]
+fn persist_resumable_generation(record: &ResumableGeneration) {
+    if let Some(storage) = web_sys::window().and_then(|w| w.local_storage().ok()).flatten() {
+        if let Ok(serialized) = serde_json::to_string(record) {
+            let _ = storage.set_item(RESUMABLE_GENERATION_KEY, &serialized);
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn clear_resumable_generation() {
+    if let Some(storage) = web_sys::window().and_then(|w| w.local_storage().ok()).flatten() {
+        let _ = storage.remove_item(RESUMABLE_GENERATION_KEY);
+    }
+}
+
+/// Trims whatever prefix of `new_text` is already covered by the end of
+/// `already_have`, so re-issuing a generation that happens to restart from
+/// scratch (rather than truly resuming mid-stream) doesn't duplicate text
+/// that's already on screen. Compares by char, not byte, since token chunks
+/// don't respect UTF-8 boundaries.
+fn dedupe_overlap(already_have: &str, new_text: &str) -> String {
+    let have: Vec<char> = already_have.chars().collect();
+    let new: Vec<char> = new_text.chars().collect();
+    let max_overlap = have.len().min(new.len());
+    for len in (1..=max_overlap).rev() {
+        if have[have.len() - len..] == new[..len] {
+            return new[len..].iter().collect();
+        }
+    }
+    new_text.to_string()
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SearchResult {
     pub title: String,
     pub url: String,
     pub description: String,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
-pub struct BraveSearchResponse {
+pub struct SearchResponse {
     pub success: bool,
-    pub results: Vec<BraveSearchResult>,
+    pub results: Vec<SearchResult>,
     pub error: Option<String>,
 }
 
-#[server]
-pub async fn brave_search(query: String, api_token: String) -> Result<BraveSearchResponse, ServerFnError> {
+/// One pluggable web-search backend - each declares the credential fields it
+/// needs so the status-menu submenu can render its inputs generically instead
+/// of hardcoding a single Brave token field.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SearchProviderId {
+    Brave,
+    SearXng,
+    GoogleCse,
+    Tavily,
+}
+
+impl SearchProviderId {
+    pub const ALL: [SearchProviderId; 4] = [
+        SearchProviderId::Brave,
+        SearchProviderId::SearXng,
+        SearchProviderId::GoogleCse,
+        SearchProviderId::Tavily,
+    ];
+
+    /// Storage/dispatch key - stable identifier used in `local_storage` and
+    /// sent to the `web_search` server fn.
+    pub fn key(&self) -> &'static str {
+        match self {
+            SearchProviderId::Brave => "brave",
+            SearchProviderId::SearXng => "searxng",
+            SearchProviderId::GoogleCse => "google_cse",
+            SearchProviderId::Tavily => "tavily",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SearchProviderId::Brave => "Brave Search",
+            SearchProviderId::SearXng => "SearXNG (self-hosted)",
+            SearchProviderId::GoogleCse => "Google Custom Search",
+            SearchProviderId::Tavily => "Tavily",
+        }
+    }
+
+    /// `(field_key, field_label, is_secret)` - every field must be non-empty
+    /// for this provider to be considered configured.
+    pub fn credential_fields(&self) -> &'static [(&'static str, &'static str, bool)] {
+        match self {
+            SearchProviderId::Brave => &[("api_token", "API Token", true)],
+            SearchProviderId::SearXng => &[("base_url", "Instance URL", false)],
+            SearchProviderId::GoogleCse => &[("api_key", "API Key", true), ("cx", "Search Engine ID", false)],
+            SearchProviderId::Tavily => &[("api_token", "API Token", true)],
+        }
+    }
+
+    pub fn docs_url(&self) -> &'static str {
+        match self {
+            SearchProviderId::Brave => "https://brave.com/search/api/",
+            SearchProviderId::SearXng => "https://docs.searxng.org/",
+            SearchProviderId::GoogleCse => "https://programmablesearchengine.google.com/",
+            SearchProviderId::Tavily => "https://tavily.com/",
+        }
+    }
+
+    pub fn from_key(key: &str) -> SearchProviderId {
+        Self::ALL.into_iter().find(|p| p.key() == key).unwrap_or(SearchProviderId::Brave)
+    }
+}
+
+/// True when any credential field the given provider declares is missing or
+/// blank in `credentials` (keyed `"{provider_key}.{field_key}"`).
+pub fn search_provider_missing_credentials(provider_key: &str, credentials: &HashMap<String, String>) -> bool {
+    let provider = SearchProviderId::from_key(provider_key);
+    provider.credential_fields().iter().any(|(field_key, _, _)| {
+        credentials.get(&format!("{}.{}", provider_key, field_key))
+            .map(|v| v.trim().is_empty())
+            .unwrap_or(true)
+    })
+}
+
+async fn brave_search_impl(query: &str, api_token: &str) -> SearchResponse {
     if api_token.trim().is_empty() {
-        return Ok(BraveSearchResponse {
-            success: false,
-            results: vec![],
-            error: Some("API token is required".to_string()),
-        });
+        return SearchResponse { success: false, results: vec![], error: Some("API token is required".to_string()) };
     }
 
     let client = reqwest::Client::new();
@@ -77,7 +458,7 @@ pub async fn brave_search(query: String, api_token: String) -> Result<BraveSearc
         .get("https://api.search.brave.com/res/v1/web/search")
         .header("X-Subscription-Token", api_token.trim())
         .header("Accept", "application/json")
-        .query(&[("q", query.as_str()), ("count", "5")])
+        .query(&[("q", query), ("count", "5")])
         .send()
         .await;
 
@@ -85,13 +466,13 @@ pub async fn brave_search(query: String, api_token: String) -> Result<BraveSearc
         Ok(response) => {
             if response.status().is_success() {
                 if let Ok(json) = response.json::<serde_json::Value>().await {
-                    let results: Vec<BraveSearchResult> = json["web"]["results"]
+                    let results: Vec<SearchResult> = json["web"]["results"]
                         .as_array()
                         .map(|arr| {
                             arr.iter()
                                 .take(5)
                                 .filter_map(|r| {
-                                    Some(BraveSearchResult {
+                                    Some(SearchResult {
                                         title: r["title"].as_str()?.to_string(),
                                         url: r["url"].as_str()?.to_string(),
                                         description: r["description"].as_str().unwrap_or("").to_string(),
@@ -100,12 +481,7 @@ pub async fn brave_search(query: String, api_token: String) -> Result<BraveSearc
                                 .collect()
                         })
                         .unwrap_or_default();
-
-                    return Ok(BraveSearchResponse {
-                        success: true,
-                        results,
-                        error: None,
-                    });
+                    return SearchResponse { success: true, results, error: None };
                 }
             } else {
                 let status = response.status();
@@ -116,85 +492,957 @@ pub async fn brave_search(query: String, api_token: String) -> Result<BraveSearc
                 } else {
                     format!("API error: {}", status)
                 };
-                return Ok(BraveSearchResponse {
-                    success: false,
-                    results: vec![],
-                    error: Some(error_msg),
-                });
+                return SearchResponse { success: false, results: vec![], error: Some(error_msg) };
             }
         }
-        Err(e) => {
-            return Ok(BraveSearchResponse {
-                success: false,
-                results: vec![],
-                error: Some(format!("Request failed: {}", e)),
-            });
+        Err(e) => return SearchResponse { success: false, results: vec![], error: Some(format!("Request failed: {}", e)) },
+    }
+
+    SearchResponse { success: false, results: vec![], error: Some("Unknown error".to_string()) }
+}
+
+async fn searxng_search_impl(query: &str, base_url: &str) -> SearchResponse {
+    if base_url.trim().is_empty() {
+        return SearchResponse { success: false, results: vec![], error: Some("Instance URL is required".to_string()) };
+    }
+    let Some(client) = safe_proxy_client(base_url).await else {
+        return SearchResponse { success: false, results: vec![], error: Some("This instance URL is not allowed".to_string()) };
+    };
+
+    let res = client
+        .get(format!("{}/search", base_url.trim().trim_end_matches('/')))
+        .query(&[("q", query), ("format", "json")])
+        .send()
+        .await;
+
+    match res {
+        Ok(response) if response.status().is_success() => match response.json::<serde_json::Value>().await {
+            Ok(json) => {
+                let results: Vec<SearchResult> = json["results"]
+                    .as_array()
+                    .map(|arr| {
+                        arr.iter()
+                            .take(5)
+                            .filter_map(|r| {
+                                Some(SearchResult {
+                                    title: r["title"].as_str()?.to_string(),
+                                    url: r["url"].as_str()?.to_string(),
+                                    description: r["content"].as_str().unwrap_or("").to_string(),
+                                })
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                SearchResponse { success: true, results, error: None }
+            }
+            Err(_) => SearchResponse { success: false, results: vec![], error: Some("Invalid response from instance".to_string()) },
+        },
+        Ok(response) => SearchResponse { success: false, results: vec![], error: Some(format!("API error: {}", response.status())) },
+        Err(e) => SearchResponse { success: false, results: vec![], error: Some(format!("Request failed: {}", e)) },
+    }
+}
+
+async fn google_cse_search_impl(query: &str, api_key: &str, cx: &str) -> SearchResponse {
+    if api_key.trim().is_empty() || cx.trim().is_empty() {
+        return SearchResponse { success: false, results: vec![], error: Some("API key and Search Engine ID are required".to_string()) };
+    }
+
+    let client = reqwest::Client::new();
+    let res = client
+        .get("https://www.googleapis.com/customsearch/v1")
+        .query(&[("key", api_key.trim()), ("cx", cx.trim()), ("q", query), ("num", "5")])
+        .send()
+        .await;
+
+    match res {
+        Ok(response) if response.status().is_success() => match response.json::<serde_json::Value>().await {
+            Ok(json) => {
+                let results: Vec<SearchResult> = json["items"]
+                    .as_array()
+                    .map(|arr| {
+                        arr.iter()
+                            .take(5)
+                            .filter_map(|r| {
+                                Some(SearchResult {
+                                    title: r["title"].as_str()?.to_string(),
+                                    url: r["link"].as_str()?.to_string(),
+                                    description: r["snippet"].as_str().unwrap_or("").to_string(),
+                                })
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                SearchResponse { success: true, results, error: None }
+            }
+            Err(_) => SearchResponse { success: false, results: vec![], error: Some("Invalid response from Google".to_string()) },
+        },
+        Ok(response) => {
+            let status = response.status();
+            let error_msg = if status.as_u16() == 403 { "Invalid API key".to_string() } else { format!("API error: {}", status) };
+            SearchResponse { success: false, results: vec![], error: Some(error_msg) }
+        }
+        Err(e) => SearchResponse { success: false, results: vec![], error: Some(format!("Request failed: {}", e)) },
+    }
+}
+
+async fn tavily_search_impl(query: &str, api_token: &str) -> SearchResponse {
+    if api_token.trim().is_empty() {
+        return SearchResponse { success: false, results: vec![], error: Some("API token is required".to_string()) };
+    }
+
+    let client = reqwest::Client::new();
+    let res = client
+        .post("https://api.tavily.com/search")
+        .json(&serde_json::json!({ "api_key": api_token.trim(), "query": query, "max_results": 5 }))
+        .send()
+        .await;
+
+    match res {
+        Ok(response) if response.status().is_success() => match response.json::<serde_json::Value>().await {
+            Ok(json) => {
+                let results: Vec<SearchResult> = json["results"]
+                    .as_array()
+                    .map(|arr| {
+                        arr.iter()
+                            .take(5)
+                            .filter_map(|r| {
+                                Some(SearchResult {
+                                    title: r["title"].as_str()?.to_string(),
+                                    url: r["url"].as_str()?.to_string(),
+                                    description: r["content"].as_str().unwrap_or("").to_string(),
+                                })
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                SearchResponse { success: true, results, error: None }
+            }
+            Err(_) => SearchResponse { success: false, results: vec![], error: Some("Invalid response from Tavily".to_string()) },
+        },
+        Ok(response) => {
+            let status = response.status();
+            let error_msg = if status.as_u16() == 401 { "Invalid API token".to_string() } else { format!("API error: {}", status) };
+            SearchResponse { success: false, results: vec![], error: Some(error_msg) }
+        }
+        Err(e) => SearchResponse { success: false, results: vec![], error: Some(format!("Request failed: {}", e)) },
+    }
+}
+
+/// Dispatches a web search to whichever provider `provider` names, reading
+/// that provider's declared credential fields out of `credentials` (keyed
+/// `"{provider_key}.{field_key}"`, matching the client-side storage scheme).
+#[server]
+pub async fn web_search(provider: String, query: String, credentials: HashMap<String, String>) -> Result<SearchResponse, ServerFnError> {
+    let field = |key: &str| credentials.get(&format!("{}.{}", provider, key)).cloned().unwrap_or_default();
+
+    Ok(match SearchProviderId::from_key(&provider) {
+        SearchProviderId::Brave => brave_search_impl(&query, &field("api_token")).await,
+        SearchProviderId::SearXng => searxng_search_impl(&query, &field("base_url")).await,
+        SearchProviderId::GoogleCse => google_cse_search_impl(&query, &field("api_key"), &field("cx")).await,
+        SearchProviderId::Tavily => tavily_search_impl(&query, &field("api_token")).await,
+    })
+}
+
+#[server]
+pub async fn test_search_provider(provider: String, credentials: HashMap<String, String>) -> Result<SearchResponse, ServerFnError> {
+    web_search(provider, "test query".to_string(), credentials).await
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct OpenAiModelsResponse {
+    pub success: bool,
+    pub models: Vec<String>,
+    pub error: Option<String>,
+}
+
+/// Lists models from an OpenAI-compatible server's `GET {base}/models`
+/// endpoint (the `{"data":[{"id":...}]}` schema shared by OpenAI, groq,
+/// and local servers like llama.cpp).
+#[server]
+pub async fn get_openai_models(base_url: String, api_key: String) -> Result<OpenAiModelsResponse, ServerFnError> {
+    let base = base_url.trim().trim_end_matches('/');
+    if base.is_empty() {
+        return Ok(OpenAiModelsResponse {
+            success: false,
+            models: vec![],
+            error: Some("Base URL is required".to_string()),
+        });
+    }
+    let Some(client) = safe_proxy_client(base).await else {
+        return Ok(OpenAiModelsResponse {
+            success: false,
+            models: vec![],
+            error: Some("This base URL is not allowed".to_string()),
+        });
+    };
+
+    let mut req = client.get(format!("{}/models", base));
+    if !api_key.trim().is_empty() {
+        req = req.bearer_auth(api_key.trim());
+    }
+
+    match req.send().await {
+        Ok(response) => {
+            if response.status().is_success() {
+                if let Ok(json) = response.json::<serde_json::Value>().await {
+                    let models: Vec<String> = json["data"]
+                        .as_array()
+                        .map(|arr| arr.iter().filter_map(|m| m["id"].as_str().map(|s| s.to_string())).collect())
+                        .unwrap_or_default();
+                    return Ok(OpenAiModelsResponse { success: true, models, error: None });
+                }
+                Ok(OpenAiModelsResponse { success: false, models: vec![], error: Some("Unexpected response format".to_string()) })
+            } else {
+                let status = response.status();
+                let error_msg = if status.as_u16() == 401 {
+                    "Invalid API key".to_string()
+                } else {
+                    format!("API error: {}", status)
+                };
+                Ok(OpenAiModelsResponse { success: false, models: vec![], error: Some(error_msg) })
+            }
         }
+        Err(e) => Ok(OpenAiModelsResponse { success: false, models: vec![], error: Some(format!("Request failed: {}", e)) }),
     }
+}
 
-    Ok(BraveSearchResponse {
-        success: false,
-        results: vec![],
-        error: Some("Unknown error".to_string()),
+#[server]
+pub async fn get_hostname() -> Result<String, ServerFnError> {
+    // Try to get hostname from system
+    if let Ok(hostname) = std::fs::read_to_string("/etc/hostname") {
+        let hostname = hostname.trim().to_string();
+        if !hostname.is_empty() {
+            return Ok(hostname);
+        }
+    }
+
+    // Fallback: try HOSTNAME env var
+    if let Ok(hostname) = std::env::var("HOSTNAME") {
+        if !hostname.is_empty() {
+            return Ok(hostname);
+        }
+    }
+
+    // Fallback: try running hostname command
+    if let Ok(output) = std::process::Command::new("hostname").output() {
+        if output.status.success() {
+            let hostname = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            if !hostname.is_empty() {
+                return Ok(hostname);
+            }
+        }
+    }
+
+    Ok("ollama".to_string())
+}
+
+/// A delete awaiting confirmation in the `delete-confirm` modal - either the
+/// local runner's model, or a model on one of the user's registered remote
+/// servers. Carries everything the confirmed action needs to dispatch.
+#[derive(Clone, Debug)]
+pub enum PendingDelete {
+    Local(String),
+    Remote(RemoteServer, String),
+}
+
+impl PendingDelete {
+    fn model_name(&self) -> &str {
+        match self {
+            PendingDelete::Local(model) => model,
+            PendingDelete::Remote(_, model) => model,
+        }
+    }
+}
+
+/// Which `add-model-section` opened the catalog browser - determines what a
+/// tap on an entry's "Pull" button does, since "pulling" a cloud model just
+/// means naming it rather than actually downloading anything.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CatalogTarget {
+    Local,
+    Cloud,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PullProgress {
+    pub model: String,
+    pub status: String,
+    pub percent: f32,
+    pub done: bool,
+    pub error: Option<String>,
+    pub bytes_downloaded: u64,
+    pub total_bytes: u64,
+    /// Formatted, EMA-smoothed throughput, e.g. "12.3 MB/s"; until a rate is
+    /// known, the bytes downloaded so far instead (e.g. "12.3 MB").
+    pub speed: String,
+    /// Formatted time remaining, e.g. "01:32" or "1h 4m".
+    pub eta: String,
+    /// Smoothed bytes/sec (new = α·instantaneous + (1−α)·prev), carried
+    /// between updates so `speed`/`eta` don't jitter between NDJSON chunks.
+    pub speed_bps: f64,
+    pub last_update: i64, // unix seconds, used for staleness detection
+    pub last_sample_ms: i64, // unix ms, baseline for the next speed_bps sample
+}
+
+// Global state for tracking pull progress (simple approach using lazy_static would be better but this works)
+use std::sync::OnceLock;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+// A backgrounded/killed Android process loses nothing in-flight: progress is
+// mirrored to a JSON file on every update and reloaded here on startup.
+const PROGRESS_STALE_SECS: i64 = 120;
+
+fn progress_file_path() -> std::path::PathBuf {
+    let dir = std::env::var("OLLAMA_APP_DATA_DIR").unwrap_or_else(|_| ".".to_string());
+    std::path::Path::new(&dir).join("pull_progress.json")
+}
+
+fn load_progress_from_disk() -> HashMap<String, PullProgress> {
+    std::fs::read_to_string(progress_file_path())
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+fn persist_progress(map: &HashMap<String, PullProgress>) {
+    if let Ok(text) = serde_json::to_string(map) {
+        let _ = std::fs::write(progress_file_path(), text);
+    }
+}
+
+static PULL_PROGRESS: OnceLock<Mutex<HashMap<String, PullProgress>>> = OnceLock::new();
+
+fn get_progress_store() -> &'static Mutex<HashMap<String, PullProgress>> {
+    PULL_PROGRESS.get_or_init(|| Mutex::new(load_progress_from_disk()))
+}
+
+/// Updates a model's progress in the store, persists the whole map to disk
+/// (so the entry survives a killed/backgrounded process), and appends a
+/// `ProgressEvent` so `/api/events` long-polls can wake up without polling.
+fn record_progress(progress: PullProgress) {
+    let store = get_progress_store();
+    let mut map = store.lock().unwrap();
+    map.insert(progress.model.clone(), progress.clone());
+    persist_progress(&map);
+    drop(map);
+    push_progress_event(progress);
+}
+
+/// One entry in the progress event log consumed by `/api/events`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ProgressEvent {
+    pub id: u64,
+    pub model: String,
+    pub status: String,
+    pub percent: f32,
+    pub bytes_downloaded: u64,
+    pub total: u64,
+    pub speed: String,
+    pub eta: String,
+    pub done: bool,
+    pub error: Option<String>,
+}
+
+static PROGRESS_EVENTS: OnceLock<Mutex<Vec<ProgressEvent>>> = OnceLock::new();
+
+fn get_progress_events_store() -> &'static Mutex<Vec<ProgressEvent>> {
+    PROGRESS_EVENTS.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+fn push_progress_event(progress: PullProgress) {
+    let mut events = get_progress_events_store().lock().unwrap();
+    let id = events.last().map(|e| e.id + 1).unwrap_or(1);
+    events.push(ProgressEvent {
+        id,
+        model: progress.model,
+        status: progress.status,
+        percent: progress.percent,
+        bytes_downloaded: progress.bytes_downloaded,
+        total: progress.total_bytes,
+        speed: progress.speed,
+        eta: progress.eta,
+        done: progress.done,
+        error: progress.error,
+    });
+    // Cap the log so a long-running app doesn't grow it without bound.
+    if events.len() > 500 {
+        let drain_to = events.len() - 500;
+        events.drain(..drain_to);
+    }
+}
+
+/// Returns events after `last_id`, optionally filtered to `models` (empty
+/// means "any model"). Used by the `/api/events` long-poll handler.
+pub fn progress_events_since(last_id: u64, models: &[String]) -> Vec<ProgressEvent> {
+    get_progress_events_store()
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|e| e.id > last_id && (models.is_empty() || models.contains(&e.model)))
+        .cloned()
+        .collect()
+}
+
+/// Where this device's Ollama server lives. `dns_override` lets a user paper
+/// over a flaky Android system resolver by pinning `host` to a literal IP,
+/// instead of relying on the platform to resolve `.local`/custom hostnames.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct OllamaEndpoint {
+    pub scheme: String,
+    pub host: String,
+    pub port: u16,
+    pub dns_override: Option<String>,
+}
+
+impl Default for OllamaEndpoint {
+    fn default() -> Self {
+        Self {
+            scheme: "http".to_string(),
+            host: "localhost".to_string(),
+            port: 11434,
+            dns_override: None,
+        }
+    }
+}
+
+impl OllamaEndpoint {
+    pub fn base_url(&self) -> String {
+        format!("{}://{}:{}", self.scheme, self.host, self.port)
+    }
+}
+
+impl Default for ModelProfile {
+    fn default() -> Self {
+        Self {
+            model: String::new(),
+            name: String::new(),
+            temperature: 0.8,
+            top_p: 0.9,
+            num_ctx: 4096,
+            repeat_penalty: 1.1,
+            system_prompt: String::new(),
+            stop: vec![],
+        }
+    }
+}
+
+static OLLAMA_ENDPOINT: OnceLock<Mutex<OllamaEndpoint>> = OnceLock::new();
+
+pub fn get_ollama_endpoint_store() -> &'static Mutex<OllamaEndpoint> {
+    OLLAMA_ENDPOINT.get_or_init(|| Mutex::new(OllamaEndpoint::default()))
+}
+
+#[server]
+pub async fn get_ollama_endpoint() -> Result<OllamaEndpoint, ServerFnError> {
+    Ok(get_ollama_endpoint_store().lock().unwrap().clone())
+}
+
+#[server]
+pub async fn set_ollama_endpoint(endpoint: OllamaEndpoint) -> Result<bool, ServerFnError> {
+    *get_ollama_endpoint_store().lock().unwrap() = endpoint;
+    Ok(true)
+}
+
+/// Builds a client pointed at `endpoint`, pinning DNS resolution for its
+/// host to `dns_override` when set so a misbehaving device resolver can't
+/// break model discovery.
+pub fn ollama_client(endpoint: &OllamaEndpoint) -> reqwest::Client {
+    let mut builder = reqwest::ClientBuilder::new();
+    if let Some(ip) = &endpoint.dns_override {
+        if let Ok(addr) = ip.parse::<std::net::IpAddr>() {
+            builder = builder.resolve(&endpoint.host, std::net::SocketAddr::new(addr, endpoint.port));
+        }
+    }
+    builder.build().unwrap_or_else(|_| reqwest::Client::new())
+}
+
+/// A user-registered remote Ollama server (e.g. a desktop box on the LAN),
+/// distinct from the primary `OllamaEndpoint` the "ollama local" runner talks
+/// to. Each one gets its own `runner-item`, model list, and add/pull/delete
+/// flow, all routed through `base_url` instead of the local endpoint.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RemoteServer {
+    pub id: String,
+    pub name: String,
+    pub base_url: String,
+}
+
+fn remote_servers_file_path() -> std::path::PathBuf {
+    let dir = std::env::var("OLLAMA_APP_DATA_DIR").unwrap_or_else(|_| ".".to_string());
+    std::path::Path::new(&dir).join("remote_servers.json")
+}
+
+fn load_remote_servers_from_disk() -> Vec<RemoteServer> {
+    std::fs::read_to_string(remote_servers_file_path())
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+fn persist_remote_servers(servers: &[RemoteServer]) {
+    if let Ok(text) = serde_json::to_string(servers) {
+        let _ = std::fs::write(remote_servers_file_path(), text);
+    }
+}
+
+static REMOTE_SERVERS: OnceLock<Mutex<Vec<RemoteServer>>> = OnceLock::new();
+
+fn get_remote_servers_store() -> &'static Mutex<Vec<RemoteServer>> {
+    REMOTE_SERVERS.get_or_init(|| Mutex::new(load_remote_servers_from_disk()))
+}
+
+/// Host (not scheme, not path) an operator has explicitly vouched for this
+/// server proxying `remote:`/`openai:`/search requests to, via
+/// `OLLAMA_ALLOWED_PROXY_HOSTS` (comma-separated `host` or `host:port`).
+/// `None` when unset. This is the only way to let this process reach a
+/// loopback/link-local/private/unique-local address - see
+/// `safe_proxy_client` - since that's also the address space most of
+/// these features legitimately want to reach (a "remote" Ollama box is
+/// usually just another machine on the same LAN).
+fn allowed_proxy_hosts() -> Option<Vec<String>> {
+    std::env::var("OLLAMA_ALLOWED_PROXY_HOSTS").ok().map(|raw| {
+        raw.split(',').map(|h| h.trim().to_string()).filter(|h| !h.is_empty()).collect()
+    })
+}
+
+/// Host and port a client-supplied base URL would actually be dialed on.
+/// Parsed with the `url` crate (same parser `reqwest` uses) rather than by
+/// hand, so alternate IPv4 spellings - decimal (`2852039166`), hex
+/// (`0xA9FEA9FE`), octal, or a trailing dot - get normalized to the address
+/// they really resolve to instead of slipping past a literal-string check.
+fn proxy_host_and_port(base_url: &str) -> Option<(String, u16)> {
+    let url = reqwest::Url::parse(base_url.trim()).ok()?;
+    let host = url.host_str()?.to_string();
+    let port = url.port_or_known_default().unwrap_or(80);
+    Some((host, port))
+}
+
+/// Whether `ip` is in address space this process should never fetch on a
+/// caller's behalf: loopback, link-local (this also covers the AWS/GCP/Azure
+/// cloud-metadata address `169.254.169.254` and the ECS task-metadata
+/// address `169.254.170.2` - both are link-local), RFC1918 private space, and
+/// IPv6 unique-local (`fc00::/7`).
+fn is_disallowed_proxy_ip(ip: std::net::IpAddr) -> bool {
+    match ip {
+        std::net::IpAddr::V4(v4) => {
+            v4.is_loopback() || v4.is_link_local() || v4.is_private() || v4.is_unspecified() || v4.is_broadcast()
+        }
+        std::net::IpAddr::V6(v6) => v6.is_loopback() || v6.is_unicast_link_local() || v6.is_unique_local() || v6.is_unspecified(),
+    }
+}
+
+/// Resolves `host` to every address it could actually connect to. A literal
+/// IP resolves to itself; a hostname is looked up via DNS and *every*
+/// returned address is returned (not just the first) so a rebinding attempt -
+/// a name that answers with both a public address and `169.254.169.254` - is
+/// still caught by the all-of check in `safe_proxy_client`, regardless of
+/// which record this process or the eventual `reqwest` connect happens to
+/// pick.
+async fn resolve_proxy_host(host: &str, port: u16) -> Vec<std::net::IpAddr> {
+    if let Ok(ip) = host.parse::<std::net::IpAddr>() {
+        return vec![ip];
+    }
+    tokio::net::lookup_host((host, port))
+        .await
+        .map(|addrs| addrs.map(|a| a.ip()).collect())
+        .unwrap_or_default()
+}
+
+/// Rejects a client-supplied base URL before this server proxies a request to
+/// it, then returns a `reqwest::Client` with DNS resolution for that host
+/// pinned to the exact address(es) just validated - the same way
+/// `ollama_client`'s `dns_override` pins the local endpoint. Without this,
+/// the check and the eventual `reqwest` connect would each resolve the
+/// hostname independently, and a DNS-rebinding attacker could answer the
+/// check with a public address and the connect with a private/loopback one.
+///
+/// Resolves the host (handling alternate IPv4 encodings and DNS rebinding,
+/// see `proxy_host_and_port`/`resolve_proxy_host`) and rejects it unless
+/// every address it resolves to is outside loopback/link-local/private/
+/// unique-local space - unless an operator explicitly vouched for this exact
+/// host via `OLLAMA_ALLOWED_PROXY_HOSTS`, which bypasses the range check (and
+/// the pinning, since the operator already vouched for wherever it resolves)
+/// entirely (e.g. to allow a LAN `remote:` server). `remote:` requests also
+/// have to match the registered server list on top of this - see
+/// `is_registered_remote_base_url`.
+pub async fn safe_proxy_client(base_url: &str) -> Option<reqwest::Client> {
+    let (host, port) = proxy_host_and_port(base_url)?;
+
+    if let Some(allowed) = allowed_proxy_hosts() {
+        if allowed.iter().any(|a| a == &host || a == &format!("{host}:{port}")) {
+            return Some(reqwest::Client::new());
+        }
+    }
+
+    let ips = resolve_proxy_host(&host, port).await;
+    if ips.is_empty() || ips.iter().any(|ip| is_disallowed_proxy_ip(*ip)) {
+        return None;
+    }
+
+    let mut builder = reqwest::ClientBuilder::new();
+    for ip in &ips {
+        builder = builder.resolve(&host, std::net::SocketAddr::new(*ip, port));
+    }
+    builder.build().ok()
+}
+
+/// Whether `base_url` matches a server the user has actually registered via
+/// `add_remote_server` - a `remote:`-prefixed model is only ever supposed to
+/// talk to one of these, so this rejects a base URL a client forged without
+/// going through that registration flow.
+pub fn is_registered_remote_base_url(base_url: &str) -> bool {
+    let normalized = base_url.trim().trim_end_matches('/');
+    get_remote_servers_store().lock().unwrap().iter().any(|s| s.base_url == normalized)
+}
+
+#[server]
+pub async fn list_remote_servers() -> Result<Vec<RemoteServer>, ServerFnError> {
+    Ok(get_remote_servers_store().lock().unwrap().clone())
+}
+
+#[server]
+pub async fn add_remote_server(name: String, base_url: String) -> Result<RemoteServer, ServerFnError> {
+    let server = RemoteServer {
+        id: format!("{:x}", std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos()),
+        name: name.trim().to_string(),
+        base_url: base_url.trim().trim_end_matches('/').to_string(),
+    };
+
+    let mut store = get_remote_servers_store().lock().unwrap();
+    store.push(server.clone());
+    persist_remote_servers(&store);
+    Ok(server)
+}
+
+#[server]
+pub async fn remove_remote_server(id: String) -> Result<bool, ServerFnError> {
+    let mut store = get_remote_servers_store().lock().unwrap();
+    store.retain(|s| s.id != id);
+    persist_remote_servers(&store);
+    Ok(true)
+}
+
+/// Lists the models installed on a remote Ollama server by hitting its
+/// `/api/tags`, mirroring `get_ollama_status` but against a caller-supplied
+/// base URL instead of the configured local endpoint.
+#[server]
+pub async fn list_remote_models(base_url: String) -> Result<StatusResponse, ServerFnError> {
+    if !is_registered_remote_base_url(&base_url) {
+        return Ok(StatusResponse { running: false, models: vec![] });
+    }
+    let Some(client) = safe_proxy_client(&base_url).await else {
+        return Ok(StatusResponse { running: false, models: vec![] });
+    };
+
+    let res = client.get(format!("{}/api/tags", base_url)).send().await;
+
+    match res {
+        Ok(response) => match response.json::<serde_json::Value>().await {
+            Ok(json) => {
+                let models = json["models"]
+                    .as_array()
+                    .map(|arr| arr.iter().filter_map(|m| m["name"].as_str().map(String::from)).collect())
+                    .unwrap_or_default();
+                Ok(StatusResponse { running: true, models })
+            }
+            Err(_) => Ok(StatusResponse { running: true, models: vec![] }),
+        },
+        Err(_) => Ok(StatusResponse { running: false, models: vec![] }),
+    }
+}
+
+/// Deletes a model from a remote server via `DELETE /api/delete` - unlike
+/// the local runner's `delete_model`, there's no `ollama` CLI to shell out
+/// to on someone else's machine.
+#[server]
+pub async fn delete_remote_model(base_url: String, model_name: String) -> Result<bool, ServerFnError> {
+    if !is_registered_remote_base_url(&base_url) {
+        return Ok(false);
+    }
+    let Some(client) = safe_proxy_client(&base_url).await else {
+        return Ok(false);
+    };
+
+    let res = client
+        .delete(format!("{}/api/delete", base_url))
+        .json(&serde_json::json!({ "name": model_name.trim() }))
+        .send()
+        .await;
+
+    Ok(res.map(|r| r.status().is_success()).unwrap_or(false))
+}
+
+#[server]
+pub async fn start_remote_model_pull(server_id: String, base_url: String, model_name: String) -> Result<PullProgress, ServerFnError> {
+    let progress_key = format!("remote:{}:{}", server_id, model_name.trim());
+
+    let pinned_client = if is_registered_remote_base_url(&base_url) { safe_proxy_client(&base_url).await } else { None };
+    if pinned_client.is_none() {
+        return Ok(PullProgress {
+            model: progress_key,
+            status: "Error".to_string(),
+            percent: 0.0,
+            done: true,
+            error: Some("Unknown or disallowed remote server".to_string()),
+            bytes_downloaded: 0,
+            total_bytes: 0,
+            speed: "".to_string(),
+            eta: "".to_string(),
+            speed_bps: 0.0,
+            last_update: 0,
+            last_sample_ms: 0,
+        });
+    }
+
+    record_progress(PullProgress {
+        model: progress_key.clone(),
+        status: "Starting...".to_string(),
+        percent: 0.0,
+        done: false,
+        error: None,
+        bytes_downloaded: 0,
+        total_bytes: 0,
+        speed: "".to_string(),
+        eta: "".to_string(),
+        speed_bps: 0.0,
+        last_update: 0,
+        last_sample_ms: 0,
+    });
+
+    tokio::spawn(run_pull_stream(pinned_client.expect("checked above"), base_url, progress_key.clone(), model_name.trim().to_string()));
+
+    Ok(PullProgress {
+        model: progress_key,
+        status: "Starting...".to_string(),
+        percent: 0.0,
+        done: false,
+        error: None,
+        bytes_downloaded: 0,
+        total_bytes: 0,
+        speed: "".to_string(),
+        eta: "".to_string(),
+        speed_bps: 0.0,
+        last_update: 0,
+        last_sample_ms: 0,
     })
 }
 
-#[server]
-pub async fn test_brave_api(api_token: String) -> Result<BraveSearchResponse, ServerFnError> {
-    brave_search("test query".to_string(), api_token).await
+/// One entry in the in-app model catalog - enough to render a searchable
+/// list and kick off a pull without round-tripping to `ollama.com/library`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CatalogEntry {
+    pub name: String,
+    pub description: String,
+    pub sizes: Vec<String>,
+}
+
+/// Bundled into the binary rather than fetched, since the whole point is to
+/// keep browsing working even when only the Ollama index (not a browser) is
+/// reachable. Trimmed to the models people actually pull; update this list
+/// alongside Ollama library releases.
+fn model_catalog() -> Vec<CatalogEntry> {
+    vec![
+        CatalogEntry { name: "llama3.2".to_string(), description: "Meta's latest small, fast Llama model".to_string(), sizes: vec!["1b".to_string(), "3b".to_string()] },
+        CatalogEntry { name: "llama3.1".to_string(), description: "Meta's Llama 3.1, strong general-purpose model".to_string(), sizes: vec!["8b".to_string(), "70b".to_string(), "405b".to_string()] },
+        CatalogEntry { name: "qwen2.5".to_string(), description: "Alibaba's Qwen 2.5, good at coding and multilingual tasks".to_string(), sizes: vec!["0.5b".to_string(), "1.5b".to_string(), "7b".to_string(), "14b".to_string(), "32b".to_string(), "72b".to_string()] },
+        CatalogEntry { name: "mistral".to_string(), description: "Mistral AI's general-purpose 7B model".to_string(), sizes: vec!["7b".to_string()] },
+        CatalogEntry { name: "gemma2".to_string(), description: "Google's Gemma 2, efficient open model".to_string(), sizes: vec!["2b".to_string(), "9b".to_string(), "27b".to_string()] },
+        CatalogEntry { name: "phi3".to_string(), description: "Microsoft's small, capable Phi-3 model".to_string(), sizes: vec!["3.8b".to_string(), "14b".to_string()] },
+        CatalogEntry { name: "deepseek-r1".to_string(), description: "DeepSeek's reasoning-focused model".to_string(), sizes: vec!["1.5b".to_string(), "7b".to_string(), "8b".to_string(), "32b".to_string(), "70b".to_string()] },
+        CatalogEntry { name: "codellama".to_string(), description: "Llama fine-tuned for code generation".to_string(), sizes: vec!["7b".to_string(), "13b".to_string(), "34b".to_string()] },
+        CatalogEntry { name: "llava".to_string(), description: "Multimodal model with vision support".to_string(), sizes: vec!["7b".to_string(), "13b".to_string(), "34b".to_string()] },
+        CatalogEntry { name: "nomic-embed-text".to_string(), description: "Text embedding model".to_string(), sizes: vec!["137m".to_string()] },
+    ]
 }
 
 #[server]
-pub async fn get_hostname() -> Result<String, ServerFnError> {
-    // Try to get hostname from system
-    if let Ok(hostname) = std::fs::read_to_string("/etc/hostname") {
-        let hostname = hostname.trim().to_string();
-        if !hostname.is_empty() {
-            return Ok(hostname);
-        }
-    }
+pub async fn list_model_catalog() -> Result<Vec<CatalogEntry>, ServerFnError> {
+    Ok(model_catalog())
+}
 
-    // Fallback: try HOSTNAME env var
-    if let Ok(hostname) = std::env::var("HOSTNAME") {
-        if !hostname.is_empty() {
-            return Ok(hostname);
-        }
-    }
+/// A named set of generation parameters for one model - e.g. a "creative"
+/// and a "precise" profile for the same local model - merged into the
+/// `options` object of the chat request when active.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ModelProfile {
+    pub model: String,
+    pub name: String,
+    pub temperature: f32,
+    pub top_p: f32,
+    pub num_ctx: u32,
+    pub repeat_penalty: f32,
+    pub system_prompt: String,
+    pub stop: Vec<String>,
+}
 
-    // Fallback: try running hostname command
-    if let Ok(output) = std::process::Command::new("hostname").output() {
-        if output.status.success() {
-            let hostname = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            if !hostname.is_empty() {
-                return Ok(hostname);
-            }
-        }
+fn model_profiles_file_path() -> std::path::PathBuf {
+    let dir = std::env::var("OLLAMA_APP_DATA_DIR").unwrap_or_else(|_| ".".to_string());
+    std::path::Path::new(&dir).join("model_profiles.json")
+}
+
+fn load_model_profiles_from_disk() -> Vec<ModelProfile> {
+    std::fs::read_to_string(model_profiles_file_path())
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+fn persist_model_profiles(profiles: &[ModelProfile]) {
+    if let Ok(text) = serde_json::to_string(profiles) {
+        let _ = std::fs::write(model_profiles_file_path(), text);
     }
+}
 
-    Ok("ollama".to_string())
+static MODEL_PROFILES: OnceLock<Mutex<Vec<ModelProfile>>> = OnceLock::new();
+
+fn get_model_profiles_store() -> &'static Mutex<Vec<ModelProfile>> {
+    MODEL_PROFILES.get_or_init(|| Mutex::new(load_model_profiles_from_disk()))
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
-pub struct PullProgress {
-    pub model: String,
-    pub status: String,
-    pub percent: f32,
-    pub done: bool,
-    pub error: Option<String>,
-    pub bytes_downloaded: u64,
-    pub speed: String,
-    pub last_update: i64, // timestamp for speed calculation
+#[server]
+pub async fn list_model_profiles(model: String) -> Result<Vec<ModelProfile>, ServerFnError> {
+    let store = get_model_profiles_store().lock().unwrap();
+    Ok(store.iter().filter(|p| p.model == model).cloned().collect())
 }
 
-// Global state for tracking pull progress (simple approach using lazy_static would be better but this works)
-use std::sync::OnceLock;
-use std::collections::HashMap;
-use std::sync::Mutex;
+#[server]
+pub async fn save_model_profile(profile: ModelProfile) -> Result<bool, ServerFnError> {
+    let mut store = get_model_profiles_store().lock().unwrap();
+    store.retain(|p| !(p.model == profile.model && p.name == profile.name));
+    store.push(profile);
+    persist_model_profiles(&store);
+    Ok(true)
+}
 
-static PULL_PROGRESS: OnceLock<Mutex<HashMap<String, PullProgress>>> = OnceLock::new();
+#[server]
+pub async fn delete_model_profile(model: String, name: String) -> Result<bool, ServerFnError> {
+    let mut store = get_model_profiles_store().lock().unwrap();
+    store.retain(|p| !(p.model == model && p.name == name));
+    persist_model_profiles(&store);
+    Ok(true)
+}
 
-fn get_progress_store() -> &'static Mutex<HashMap<String, PullProgress>> {
-    PULL_PROGRESS.get_or_init(|| Mutex::new(HashMap::new()))
+/// Streams `POST {endpoint}/api/pull`'s NDJSON progress into the shared
+/// `PullProgress` store under `progress_key` (the "model" an `active_downloads`
+/// entry is keyed by - just the model name for the local runner, or
+/// `remote:{server_id}:{model}` for a registered remote server), computing
+/// EMA-smoothed speed/ETA along the way. Shared by `start_model_pull` and
+/// `start_remote_model_pull` so the two runners don't duplicate this loop.
+async fn run_pull_stream(client: reqwest::Client, base_url: String, progress_key: String, model_name: String) {
+    let res = client.post(format!("{}/api/pull", base_url))
+        .json(&serde_json::json!({ "name": model_name }))
+        .send()
+        .await;
+
+    match res {
+        Ok(response) => {
+            use futures::StreamExt;
+            let mut stream = response.bytes_stream();
+
+            while let Some(chunk) = stream.next().await {
+                if let Ok(bytes) = chunk {
+                    let text = String::from_utf8_lossy(&bytes);
+                    // Parse each line as JSON
+                    for line in text.lines() {
+                        if let Ok(json) = serde_json::from_str::<serde_json::Value>(line) {
+                            let prev = get_progress_store().lock().unwrap().get(&progress_key).cloned();
+
+                            let status_text = json["status"].as_str().unwrap_or("").to_string();
+                            let total = json["total"].as_u64().unwrap_or(0);
+                            let completed = json["completed"].as_u64().unwrap_or(0);
+
+                            // Get previous values to preserve if needed
+                            let prev_bytes = prev.as_ref().map(|p| p.bytes_downloaded).unwrap_or(0);
+                            let prev_speed_bps = prev.as_ref().map(|p| p.speed_bps).unwrap_or(0.0);
+                            let prev_sample_ms = prev.as_ref().map(|p| p.last_sample_ms).unwrap_or(0);
+                            let prev_percent = prev.as_ref().map(|p| p.percent).unwrap_or(0.0);
+
+                            let percent = if total > 0 {
+                                (completed as f32 / total as f32) * 100.0
+                            } else {
+                                prev_percent // Keep previous percent if no new data
+                            };
+
+                            let now_ms = std::time::SystemTime::now()
+                                .duration_since(std::time::UNIX_EPOCH)
+                                .unwrap_or_default()
+                                .as_millis() as i64;
+
+                            // Smooth the real byte/time deltas with an EMA (α≈0.3) so the
+                            // displayed throughput doesn't jitter between NDJSON chunks.
+                            const EMA_ALPHA: f64 = 0.3;
+                            let (speed_bps, last_sample_ms) = if prev_sample_ms > 0 && completed > prev_bytes {
+                                let dt_secs = (now_ms - prev_sample_ms).max(1) as f64 / 1000.0;
+                                let instantaneous = (completed - prev_bytes) as f64 / dt_secs;
+                                let smoothed = if prev_speed_bps > 0.0 {
+                                    EMA_ALPHA * instantaneous + (1.0 - EMA_ALPHA) * prev_speed_bps
+                                } else {
+                                    instantaneous
+                                };
+                                (smoothed, now_ms)
+                            } else {
+                                // First sample for this model, or no new bytes yet: nothing to
+                                // derive a rate from, so carry the previous estimate forward.
+                                (prev_speed_bps, if prev_sample_ms > 0 { prev_sample_ms } else { now_ms })
+                            };
+
+                            let speed = if speed_bps > 0.0 {
+                                format!("{}/s", format_bytes(speed_bps as u64))
+                            } else if completed > 0 {
+                                format_bytes(completed)
+                            } else {
+                                "".to_string()
+                            };
+
+                            let eta = if speed_bps > 0.0 && total > completed {
+                                format_duration(((total - completed) as f64 / speed_bps) as u64)
+                            } else {
+                                "".to_string()
+                            };
+
+                            let is_done = status_text == "success" || json.get("error").is_some();
+                            let error = json["error"].as_str().map(|s| s.to_string());
+
+                            record_progress(PullProgress {
+                                model: progress_key.clone(),
+                                status: if is_done && error.is_none() { "Complete".to_string() } else { status_text },
+                                percent: if is_done && error.is_none() { 100.0 } else { percent },
+                                done: is_done,
+                                error,
+                                bytes_downloaded: completed,
+                                total_bytes: total,
+                                speed: if is_done { "".to_string() } else { speed },
+                                eta: if is_done { "".to_string() } else { eta },
+                                speed_bps,
+                                last_update: now_ms / 1000,
+                                last_sample_ms,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        Err(e) => {
+            record_progress(PullProgress {
+                model: progress_key,
+                status: "Error".to_string(),
+                percent: 0.0,
+                done: true,
+                error: Some(e.to_string()),
+                bytes_downloaded: 0,
+                total_bytes: 0,
+                speed: "".to_string(),
+                eta: "".to_string(),
+                speed_bps: 0.0,
+                last_update: 0,
+                last_sample_ms: 0,
+            });
+        }
+    }
 }
 
 #[server]
@@ -209,8 +1457,12 @@ pub async fn start_model_pull(model_name: String) -> Result<PullProgress, Server
             done: true,
             error: Some("Model name cannot be empty".to_string()),
             bytes_downloaded: 0,
+            total_bytes: 0,
             speed: "".to_string(),
+            eta: "".to_string(),
+            speed_bps: 0.0,
             last_update: 0,
+            last_sample_ms: 0,
         });
     }
 
@@ -225,104 +1477,24 @@ pub async fn start_model_pull(model_name: String) -> Result<PullProgress, Server
     let model_clone = model.clone();
 
     // Initialize progress
-    {
-        let store = get_progress_store();
-        let mut map = store.lock().unwrap();
-        map.insert(model.clone(), PullProgress {
-            model: model.clone(),
-            status: "Starting...".to_string(),
-            percent: 0.0,
-            done: false,
-            error: None,
-            bytes_downloaded: 0,
-            speed: "".to_string(),
-            last_update: 0,
-        });
-    }
+    record_progress(PullProgress {
+        model: model.clone(),
+        status: "Starting...".to_string(),
+        percent: 0.0,
+        done: false,
+        error: None,
+        bytes_downloaded: 0,
+        total_bytes: 0,
+        speed: "".to_string(),
+        eta: "".to_string(),
+        speed_bps: 0.0,
+        last_update: 0,
+        last_sample_ms: 0,
+    });
 
     // Start the pull using Ollama API (streams JSON progress)
-    tokio::spawn(async move {
-        let client = reqwest::Client::new();
-        let res = client.post("http://localhost:11434/api/pull")
-            .json(&serde_json::json!({ "name": model_clone }))
-            .send()
-            .await;
-
-        match res {
-            Ok(response) => {
-                use futures::StreamExt;
-                let mut stream = response.bytes_stream();
-
-                while let Some(chunk) = stream.next().await {
-                    if let Ok(bytes) = chunk {
-                        let text = String::from_utf8_lossy(&bytes);
-                        // Parse each line as JSON
-                        for line in text.lines() {
-                            if let Ok(json) = serde_json::from_str::<serde_json::Value>(line) {
-                                let store = get_progress_store();
-                                let mut map = store.lock().unwrap();
-
-                                let status_text = json["status"].as_str().unwrap_or("").to_string();
-                                let total = json["total"].as_u64().unwrap_or(0);
-                                let completed = json["completed"].as_u64().unwrap_or(0);
-
-                                // Get previous values to preserve if needed
-                                let prev = map.get(&model_clone).cloned();
-                                let prev_speed = prev.as_ref().map(|p| p.speed.clone()).unwrap_or_default();
-                                let prev_percent = prev.as_ref().map(|p| p.percent).unwrap_or(0.0);
-
-                                let percent = if total > 0 {
-                                    (completed as f32 / total as f32) * 100.0
-                                } else {
-                                    prev_percent // Keep previous percent if no new data
-                                };
-
-                                // Calculate speed from completed bytes, keep previous if no new data
-                                let speed = if total > 0 && completed > 0 {
-                                    format_bytes(completed) + " / " + &format_bytes(total)
-                                } else if !prev_speed.is_empty() {
-                                    prev_speed // Keep previous speed
-                                } else {
-                                    "".to_string()
-                                };
-
-                                let is_done = status_text == "success" || json.get("error").is_some();
-                                let error = json["error"].as_str().map(|s| s.to_string());
-
-                                map.insert(model_clone.clone(), PullProgress {
-                                    model: model_clone.clone(),
-                                    status: if is_done && error.is_none() { "Complete".to_string() } else { status_text },
-                                    percent: if is_done && error.is_none() { 100.0 } else { percent },
-                                    done: is_done,
-                                    error,
-                                    bytes_downloaded: completed,
-                                    speed,
-                                    last_update: std::time::SystemTime::now()
-                                        .duration_since(std::time::UNIX_EPOCH)
-                                        .unwrap_or_default()
-                                        .as_secs() as i64,
-                                });
-                            }
-                        }
-                    }
-                }
-            }
-            Err(e) => {
-                let store = get_progress_store();
-                let mut map = store.lock().unwrap();
-                map.insert(model_clone.clone(), PullProgress {
-                    model: model_clone,
-                    status: "Error".to_string(),
-                    percent: 0.0,
-                    done: true,
-                    error: Some(e.to_string()),
-                    bytes_downloaded: 0,
-                    speed: "".to_string(),
-                    last_update: 0,
-                });
-            }
-        }
-    });
+    let endpoint = get_ollama_endpoint_store().lock().unwrap().clone();
+    tokio::spawn(run_pull_stream(ollama_client(&endpoint), endpoint.base_url(), model_clone.clone(), model_clone));
 
     Ok(PullProgress {
         model: model_name.trim().to_string(),
@@ -331,8 +1503,12 @@ pub async fn start_model_pull(model_name: String) -> Result<PullProgress, Server
         done: false,
         error: None,
         bytes_downloaded: 0,
+        total_bytes: 0,
         speed: "".to_string(),
+        eta: "".to_string(),
+        speed_bps: 0.0,
         last_update: 0,
+        last_sample_ms: 0,
     })
 }
 
@@ -352,6 +1528,19 @@ fn format_bytes(bytes: u64) -> String {
     }
 }
 
+/// Formats a duration as `mm:ss` under an hour, `Hh Mm` otherwise.
+fn format_duration(total_secs: u64) -> String {
+    let hours = total_secs / 3600;
+    let minutes = (total_secs % 3600) / 60;
+    let seconds = total_secs % 60;
+
+    if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{:02}:{:02}", minutes, seconds)
+    }
+}
+
 #[server]
 pub async fn cancel_model_pull(model_name: String) -> Result<bool, ServerFnError> {
     use std::process::Command;
@@ -382,12 +1571,54 @@ pub async fn check_pull_progress(model_name: String) -> Result<PullProgress, Ser
     let model = model_name.trim().to_string();
 
     // Check progress store first
-    {
+    let existing = {
         let store = get_progress_store();
         let map = store.lock().unwrap();
-        if let Some(progress) = map.get(&model) {
-            return Ok(progress.clone());
+        map.get(&model).cloned()
+    };
+
+    if let Some(progress) = existing {
+        if progress.done {
+            return Ok(progress);
+        }
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+        let abandoned = progress.last_update > 0 && now - progress.last_update > PROGRESS_STALE_SECS;
+
+        if !abandoned {
+            return Ok(progress);
         }
+
+        // No update in a while - the process was likely killed mid-download.
+        // Reconcile against what Ollama actually has before giving up on it.
+        let status = get_ollama_status().await?;
+        let model_exists = status.models.iter().any(|m| m.starts_with(&model) || m.contains(&model));
+
+        if model_exists {
+            let done = PullProgress {
+                model: model.clone(),
+                status: "Complete".to_string(),
+                percent: 100.0,
+                done: true,
+                error: None,
+                bytes_downloaded: progress.bytes_downloaded,
+                total_bytes: progress.total_bytes,
+                speed: "".to_string(),
+                eta: "".to_string(),
+                speed_bps: progress.speed_bps,
+                last_update: now,
+                last_sample_ms: progress.last_sample_ms,
+            };
+            record_progress(done.clone());
+            return Ok(done);
+        }
+
+        // Still missing: Ollama's /api/pull resumes from whatever is already
+        // on disk, so re-issuing it picks the download back up.
+        return start_model_pull(model).await;
     }
 
     // Fallback: check if model exists (might have been pulled before tracking)
@@ -404,8 +1635,12 @@ pub async fn check_pull_progress(model_name: String) -> Result<PullProgress, Ser
             done: true,
             error: None,
             bytes_downloaded: 0,
+            total_bytes: 0,
             speed: "".to_string(),
+            eta: "".to_string(),
+            speed_bps: 0.0,
             last_update: 0,
+            last_sample_ms: 0,
         })
     } else {
         Ok(PullProgress {
@@ -415,8 +1650,12 @@ pub async fn check_pull_progress(model_name: String) -> Result<PullProgress, Ser
             done: false,
             error: None,
             bytes_downloaded: 0,
+            total_bytes: 0,
             speed: "".to_string(),
+            eta: "".to_string(),
+            speed_bps: 0.0,
             last_update: 0,
+            last_sample_ms: 0,
         })
     }
 }
@@ -441,10 +1680,11 @@ pub async fn delete_model(model_name: String) -> Result<bool, ServerFnError> {
 
 #[server]
 pub async fn get_ollama_status() -> Result<StatusResponse, ServerFnError> {
-    let client = reqwest::Client::new();
+    let endpoint = get_ollama_endpoint_store().lock().unwrap().clone();
+    let client = ollama_client(&endpoint);
 
     // Check if Ollama is running by hitting the tags endpoint
-    let res = client.get("http://localhost:11434/api/tags").send().await;
+    let res = client.get(format!("{}/api/tags", endpoint.base_url())).send().await;
 
     match res {
         Ok(response) => {
@@ -491,103 +1731,547 @@ pub async fn toggle_ollama_service() -> Result<StatusResponse, ServerFnError> {
         tokio::time::sleep(tokio::time::Duration::from_millis(1000)).await;
     }
 
-    // Return new status
-    get_ollama_status().await
+    // Return new status
+    get_ollama_status().await
+}
+
+/// An active Ollama Cloud session: the signed bearer token plus its decoded
+/// `exp` claim, so callers can tell a stale token from a missing one instead
+/// of treating both as "logged out".
+#[derive(Serialize, Deserialize, Clone)]
+struct CloudSession {
+    email: String,
+    access_token: String,
+    refresh_token: Option<String>,
+    issued_at: i64,  // unix seconds
+    expires_at: i64, // unix seconds, decoded from the token's `exp` claim
+}
+
+impl CloudSession {
+    fn is_expired(&self) -> bool {
+        now_unix_secs() >= self.expires_at
+    }
+
+    fn seconds_remaining(&self) -> i64 {
+        self.expires_at - now_unix_secs()
+    }
+}
+
+fn now_unix_secs() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+fn cloud_session_file_path() -> std::path::PathBuf {
+    let dir = std::env::var("OLLAMA_APP_DATA_DIR").unwrap_or_else(|_| ".".to_string());
+    std::path::Path::new(&dir).join("cloud_session.json")
+}
+
+/// Loads the persisted session from disk, discarding it if it's already
+/// expired so a dead session can never be resurrected by a restart.
+fn load_cloud_session_from_disk() -> Option<CloudSession> {
+    let session: CloudSession = std::fs::read_to_string(cloud_session_file_path())
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())?;
+    if session.is_expired() {
+        let _ = std::fs::remove_file(cloud_session_file_path());
+        return None;
+    }
+    Some(session)
+}
+
+fn persist_cloud_session(session: &Option<CloudSession>) {
+    match session {
+        Some(session) => {
+            if let Ok(text) = serde_json::to_string(session) {
+                let _ = std::fs::write(cloud_session_file_path(), text);
+            }
+        }
+        None => {
+            let _ = std::fs::remove_file(cloud_session_file_path());
+        }
+    }
+}
+
+// Cloud session storage
+static CLOUD_SESSION: OnceLock<Mutex<Option<CloudSession>>> = OnceLock::new();
+
+fn get_cloud_session_store() -> &'static Mutex<Option<CloudSession>> {
+    CLOUD_SESSION.get_or_init(|| Mutex::new(load_cloud_session_from_disk()))
+}
+
+/// Decodes the `exp` claim (unix seconds) out of a JWT's payload segment.
+/// Ollama Cloud signs tokens server-side and we only ever see them over TLS,
+/// so there is no local JWKS to verify the signature against; expiry is what
+/// we can and do check before every use.
+fn decode_jwt_expiry(token: &str) -> Option<i64> {
+    let payload = token.split('.').nth(1)?;
+    let bytes = base64_url_decode(payload)?;
+    let json: serde_json::Value = serde_json::from_slice(&bytes).ok()?;
+    json["exp"].as_i64()
+}
+
+fn base64_url_decode(input: &str) -> Option<Vec<u8>> {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+    let mut table = [255u8; 256];
+    for (i, &c) in ALPHABET.iter().enumerate() {
+        table[c as usize] = i as u8;
+    }
+    let mut bits: u32 = 0;
+    let mut bit_count = 0u32;
+    let mut out = Vec::new();
+    for c in input.bytes() {
+        let val = table[c as usize];
+        if val == 255 {
+            continue;
+        }
+        bits = (bits << 6) | val as u32;
+        bit_count += 6;
+        if bit_count >= 8 {
+            bit_count -= 8;
+            out.push((bits >> bit_count) as u8);
+        }
+    }
+    Some(out)
+}
+
+/// Resolves the real account email from the OAuth provider's userinfo
+/// endpoint using the access token obtained for that provider, so sessions
+/// aren't stamped with a placeholder like `"google_user"`. Returns `None` on
+/// any failure (unsupported provider, request error, missing field) so
+/// callers can fall back to something inert rather than failing the login.
+async fn resolve_oauth_email(provider: &str, access_token: &str) -> Option<String> {
+    let client = reqwest::Client::new();
+    match provider {
+        "google" => {
+            let json: serde_json::Value = client
+                .get("https://www.googleapis.com/oauth2/v3/userinfo")
+                .bearer_auth(access_token)
+                .send()
+                .await
+                .ok()?
+                .json()
+                .await
+                .ok()?;
+            json["email"].as_str().map(String::from)
+        }
+        "github" => {
+            // GitHub's `/user` email field is only populated if the user has
+            // made an email public - `/user/emails` is the reliable source.
+            let emails: Vec<serde_json::Value> = client
+                .get("https://api.github.com/user/emails")
+                .bearer_auth(access_token)
+                .header("User-Agent", "ollama-rust")
+                .header("Accept", "application/vnd.github+json")
+                .send()
+                .await
+                .ok()?
+                .json()
+                .await
+                .ok()?;
+            emails.iter()
+                .find(|e| e["primary"].as_bool().unwrap_or(false))
+                .or_else(|| emails.first())
+                .and_then(|e| e["email"].as_str())
+                .map(String::from)
+        }
+        _ => None,
+    }
+}
+
+/// Exchanges credentials for a signed bearer token against the Ollama Cloud
+/// auth endpoint and persists the resulting session.
+async fn exchange_for_session(body: serde_json::Value, email: String) -> Result<CloudLoginResponse, ServerFnError> {
+    let client = reqwest::Client::new();
+    let res = client
+        .post("https://ollama.com/api/auth/token")
+        .json(&body)
+        .send()
+        .await;
+
+    let response = match res {
+        Ok(response) if response.status().is_success() => response,
+        Ok(response) => {
+            return Ok(CloudLoginResponse {
+                success: false,
+                message: format!("Ollama Cloud rejected the login ({})", response.status()),
+                api_key: None,
+                existing_provider: None,
+            });
+        }
+        Err(_) => {
+            return Ok(CloudLoginResponse {
+                success: false,
+                message: "Ollama Cloud is unreachable".to_string(),
+                api_key: None,
+                existing_provider: None,
+            });
+        }
+    };
+
+    let json: serde_json::Value = match response.json().await {
+        Ok(json) => json,
+        Err(_) => {
+            return Ok(CloudLoginResponse {
+                success: false,
+                message: "Ollama Cloud returned an unexpected response".to_string(),
+                api_key: None,
+                existing_provider: None,
+            });
+        }
+    };
+
+    // The email is already tied to a different sign-in method - offer to
+    // link instead of failing outright.
+    if json["error"].as_str() == Some("identity_exists") {
+        let existing_provider = json["existing_provider"].as_str().unwrap_or("another sign-in method").to_string();
+        return Ok(CloudLoginResponse {
+            success: false,
+            message: format!("This email is already linked to {existing_provider}"),
+            api_key: None,
+            existing_provider: Some(existing_provider),
+        });
+    }
+
+    let Some(access_token) = json["access_token"].as_str() else {
+        return Ok(CloudLoginResponse {
+            success: false,
+            message: "Ollama Cloud response was missing an access token".to_string(),
+            api_key: None,
+            existing_provider: None,
+        });
+    };
+
+    let Some(expires_at) = decode_jwt_expiry(access_token) else {
+        return Ok(CloudLoginResponse {
+            success: false,
+            message: "Could not decode the token's expiry".to_string(),
+            api_key: None,
+            existing_provider: None,
+        });
+    };
+
+    let session = CloudSession {
+        email: email.clone(),
+        access_token: access_token.to_string(),
+        refresh_token: json["refresh_token"].as_str().map(|s| s.to_string()),
+        issued_at: now_unix_secs(),
+        expires_at,
+    };
+
+    let store = get_cloud_session_store();
+    *store.lock().unwrap() = Some(session.clone());
+    persist_cloud_session(&Some(session));
+
+    Ok(CloudLoginResponse {
+        success: true,
+        message: "Connected".to_string(),
+        api_key: Some(email),
+        existing_provider: None,
+    })
+}
+
+/// A device-authorization grant (RFC 8628) in progress - the user types
+/// `user_code` into `verification_uri` on any device while we poll the token
+/// endpoint in the background. Avoids the redirect-URI dance that's
+/// unreliable inside an Android WebView.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DeviceAuthorization {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    pub interval: i64,
+    pub expires_in: i64,
+}
+
+/// One poll of the device token endpoint. `status` mirrors the RFC 8628
+/// error codes (`pending`, `slow_down`, `expired`, `denied`) plus `success`,
+/// `identity_exists` and `error` for the terminal cases.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DevicePollResponse {
+    pub status: String,
+    pub message: Option<String>,
+    pub email: Option<String>,
+    /// Set when `status` is `"identity_exists"` - the provider the email is
+    /// already linked to.
+    pub existing_provider: Option<String>,
+}
+
+fn device_authorization_endpoint(provider: &str) -> Option<&'static str> {
+    match provider {
+        "google" => Some("https://oauth2.googleapis.com/device/code"),
+        "github" => Some("https://github.com/login/device/code"),
+        _ => None,
+    }
+}
+
+fn device_token_endpoint(provider: &str) -> Option<&'static str> {
+    match provider {
+        "google" => Some("https://oauth2.googleapis.com/token"),
+        "github" => Some("https://github.com/login/oauth/access_token"),
+        _ => None,
+    }
+}
+
+fn oauth_client_id(provider: &str) -> Option<String> {
+    match provider {
+        "google" => std::env::var("OAUTH_GOOGLE_CLIENT_ID").ok(),
+        "github" => std::env::var("OAUTH_GITHUB_CLIENT_ID").ok(),
+        _ => None,
+    }
+}
+
+fn oauth_scope(provider: &str) -> &'static str {
+    match provider {
+        "google" => "openid email",
+        "github" => "read:user user:email",
+        _ => "",
+    }
+}
+
+#[server]
+pub async fn start_device_authorization(provider: String) -> Result<DeviceAuthorization, ServerFnError> {
+    let Some(endpoint) = device_authorization_endpoint(&provider) else {
+        return Err(ServerFnError::new("Unsupported OAuth provider"));
+    };
+    let Some(client_id) = oauth_client_id(&provider) else {
+        return Err(ServerFnError::new(format!("{} sign-in is not configured on this server", provider)));
+    };
+
+    let client = reqwest::Client::new();
+    let res = client
+        .post(endpoint)
+        .header("Accept", "application/json")
+        .form(&[("client_id", client_id.as_str()), ("scope", oauth_scope(&provider))])
+        .send()
+        .await
+        .map_err(|e| ServerFnError::new(format!("Device authorization request failed: {}", e)))?;
+
+    let json: serde_json::Value = res
+        .json()
+        .await
+        .map_err(|e| ServerFnError::new(format!("Invalid device authorization response: {}", e)))?;
+
+    let device_code = json["device_code"].as_str().unwrap_or_default().to_string();
+    let user_code = json["user_code"].as_str().unwrap_or_default().to_string();
+    if device_code.is_empty() || user_code.is_empty() {
+        return Err(ServerFnError::new("Provider did not return a device code"));
+    }
+
+    Ok(DeviceAuthorization {
+        device_code,
+        user_code,
+        // GitHub calls this `verification_uri`, Google calls it `verification_url`.
+        verification_uri: json["verification_uri"].as_str()
+            .or_else(|| json["verification_url"].as_str())
+            .unwrap_or_default()
+            .to_string(),
+        interval: json["interval"].as_i64().unwrap_or(5),
+        expires_in: json["expires_in"].as_i64().unwrap_or(1800),
+    })
 }
 
-// Cloud credentials storage
-static CLOUD_CREDENTIALS: OnceLock<Mutex<Option<(String, String)>>> = OnceLock::new();
+/// One poll of the token endpoint for a device code started via
+/// `start_device_authorization`. On `success`, the provider token has
+/// already been exchanged for - and the caller has - an Ollama Cloud session.
+/// `link_with` is set when this poll is completing an account-link: the user
+/// re-authenticated against `provider` (the one that already owns the email)
+/// in order to merge it with the identity they originally tried, so the
+/// exchange is told to merge rather than create a fresh session.
+#[server]
+pub async fn poll_device_authorization(provider: String, device_code: String, link_with: Option<String>) -> Result<DevicePollResponse, ServerFnError> {
+    let Some(endpoint) = device_token_endpoint(&provider) else {
+        return Ok(DevicePollResponse { status: "error".to_string(), message: Some("Unsupported OAuth provider".to_string()), email: None, existing_provider: None });
+    };
+    let Some(client_id) = oauth_client_id(&provider) else {
+        return Ok(DevicePollResponse { status: "error".to_string(), message: Some(format!("{} sign-in is not configured on this server", provider)), email: None, existing_provider: None });
+    };
 
-fn get_cloud_credentials_store() -> &'static Mutex<Option<(String, String)>> {
-    CLOUD_CREDENTIALS.get_or_init(|| Mutex::new(None))
-}
+    let client = reqwest::Client::new();
+    let res = client
+        .post(endpoint)
+        .header("Accept", "application/json")
+        .form(&[
+            ("client_id", client_id.as_str()),
+            ("device_code", device_code.as_str()),
+            ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+        ])
+        .send()
+        .await;
 
-#[server]
-pub async fn cloud_oauth_login(provider: String) -> Result<CloudLoginResponse, ServerFnError> {
-    // Validate provider
-    if provider != "google" && provider != "github" && provider != "email" {
-        return Ok(CloudLoginResponse {
-            success: false,
-            message: "Invalid login provider".to_string(),
-            api_key: None,
+    let response = match res {
+        Ok(response) => response,
+        Err(e) => return Ok(DevicePollResponse { status: "error".to_string(), message: Some(format!("Request failed: {}", e)), email: None, existing_provider: None }),
+    };
+
+    let json: serde_json::Value = match response.json().await {
+        Ok(json) => json,
+        Err(_) => return Ok(DevicePollResponse { status: "error".to_string(), message: Some("Invalid response from provider".to_string()), email: None, existing_provider: None }),
+    };
+
+    if let Some(error) = json["error"].as_str() {
+        let status = match error {
+            "authorization_pending" => "pending",
+            "slow_down" => "slow_down",
+            "expired_token" => "expired",
+            "access_denied" => "denied",
+            _ => "error",
+        };
+        return Ok(DevicePollResponse {
+            status: status.to_string(),
+            message: json["error_description"].as_str().map(String::from),
+            email: None,
+            existing_provider: None,
         });
     }
 
-    // For demo purposes, simulate successful login
-    // TODO: Replace with actual Ollama Cloud OAuth/auth flow
-    let demo_user = match provider.as_str() {
-        "google" => "user@gmail.com",
-        "github" => "github_user",
-        "email" => "user@example.com",
-        _ => "demo_user",
+    let Some(access_token) = json["access_token"].as_str() else {
+        return Ok(DevicePollResponse { status: "error".to_string(), message: Some("Provider response was missing an access token".to_string()), email: None, existing_provider: None });
     };
 
-    let store = get_cloud_credentials_store();
-    let mut creds = store.lock().unwrap();
-    *creds = Some((demo_user.to_string(), "demo_key".to_string()));
+    let mut body = serde_json::json!({ "grant_type": "oauth", "provider": provider, "access_token": access_token });
+    if let Some(link_with) = &link_with {
+        body["link_with"] = serde_json::Value::from(link_with.clone());
+    }
 
-    Ok(CloudLoginResponse {
-        success: true,
-        message: "Connected (demo mode)".to_string(),
-        api_key: Some(demo_user.to_string()),
-    })
+    let email = resolve_oauth_email(&provider, access_token).await
+        .unwrap_or_else(|| format!("{provider}_user"));
+
+    match exchange_for_session(body, email).await {
+        Ok(response) if response.success => Ok(DevicePollResponse { status: "success".to_string(), message: None, email: response.api_key, existing_provider: None }),
+        Ok(response) if response.existing_provider.is_some() => Ok(DevicePollResponse {
+            status: "identity_exists".to_string(),
+            message: Some(response.message),
+            email: None,
+            existing_provider: response.existing_provider,
+        }),
+        Ok(response) => Ok(DevicePollResponse { status: "error".to_string(), message: Some(response.message), email: None, existing_provider: None }),
+        Err(e) => Ok(DevicePollResponse { status: "error".to_string(), message: Some(format!("{}", e)), email: None, existing_provider: None }),
+    }
 }
 
+/// `link_with` mirrors `poll_device_authorization`'s parameter of the same
+/// name: set when this email/password login is completing an account-link
+/// for a provider (e.g. "google") that collided with an email identity, so
+/// the exchange merges the two rather than just signing into the email one.
 #[server]
-pub async fn cloud_email_login(email: String, password: String) -> Result<CloudLoginResponse, ServerFnError> {
+pub async fn cloud_email_login(email: String, password: String, link_with: Option<String>) -> Result<CloudLoginResponse, ServerFnError> {
     // Validate input
     if email.trim().is_empty() || password.trim().is_empty() {
         return Ok(CloudLoginResponse {
             success: false,
             message: "Email and password are required".to_string(),
             api_key: None,
+            existing_provider: None,
         });
     }
 
-    // For demo purposes, simulate successful login
-    // TODO: Replace with actual Ollama Cloud authentication
-    let store = get_cloud_credentials_store();
-    let mut creds = store.lock().unwrap();
-    *creds = Some((email.trim().to_string(), "demo_key".to_string()));
+    let mut body = serde_json::json!({ "grant_type": "password", "email": email.trim(), "password": password });
+    if let Some(link_with) = &link_with {
+        body["link_with"] = serde_json::Value::from(link_with.clone());
+    }
 
-    Ok(CloudLoginResponse {
-        success: true,
-        message: "Connected (demo mode)".to_string(),
-        api_key: Some(email.trim().to_string()),
-    })
+    exchange_for_session(body, email.trim().to_string()).await
 }
 
 #[server]
 pub async fn cloud_logout() -> Result<bool, ServerFnError> {
-    let store = get_cloud_credentials_store();
-    let mut creds = store.lock().unwrap();
-    *creds = None;
+    let store = get_cloud_session_store();
+    *store.lock().unwrap() = None;
+    persist_cloud_session(&None);
     Ok(true)
 }
 
 #[server]
 pub async fn check_cloud_login() -> Result<Option<String>, ServerFnError> {
-    let store = get_cloud_credentials_store();
-    let creds = store.lock().unwrap();
-    Ok(creds.as_ref().map(|(email, _)| email.clone()))
+    let store = get_cloud_session_store();
+    let mut session = store.lock().unwrap();
+    match session.as_ref() {
+        Some(s) if !s.is_expired() => Ok(Some(s.email.clone())),
+        Some(_) => {
+            // Expired: purge rather than let a dead session linger in memory or on disk.
+            *session = None;
+            persist_cloud_session(&None);
+            Ok(None)
+        }
+        None => Ok(None),
+    }
+}
+
+/// Seconds remaining on the current cloud session, or `None` if there isn't one.
+#[server]
+pub async fn cloud_session_lifetime() -> Result<Option<i64>, ServerFnError> {
+    let store = get_cloud_session_store();
+    let session = store.lock().unwrap();
+    Ok(session.as_ref().map(|s| s.seconds_remaining()))
+}
+
+/// Proactively exchanges the stored refresh token for a new access token
+/// before the current one expires. Clears the session on failure so the
+/// caller can fall back to a re-login prompt instead of spinning on a dead
+/// token.
+#[server]
+pub async fn refresh_cloud_session() -> Result<CloudLoginResponse, ServerFnError> {
+    let (email, refresh_token) = {
+        let store = get_cloud_session_store();
+        let session = store.lock().unwrap();
+        match session.as_ref() {
+            Some(s) => (s.email.clone(), s.refresh_token.clone()),
+            None => {
+                return Ok(CloudLoginResponse {
+                    success: false,
+                    message: "Not logged in".to_string(),
+                    api_key: None,
+                    existing_provider: None,
+                });
+            }
+        }
+    };
+
+    let Some(refresh_token) = refresh_token else {
+        return Ok(CloudLoginResponse {
+            success: false,
+            message: "No refresh token available".to_string(),
+            api_key: None,
+            existing_provider: None,
+        });
+    };
+
+    let response = exchange_for_session(
+        serde_json::json!({ "grant_type": "refresh_token", "refresh_token": refresh_token }),
+        email,
+    )
+    .await?;
+
+    if !response.success {
+        let store = get_cloud_session_store();
+        *store.lock().unwrap() = None;
+        persist_cloud_session(&None);
+    }
+
+    Ok(response)
 }
 
 #[server]
 pub async fn get_cloud_models() -> Result<CloudModelsResponse, ServerFnError> {
-    // Check if logged in and get API key in a separate scope to release lock
-    let api_key = {
-        let store = get_cloud_credentials_store();
-        let creds = store.lock().unwrap();
-        match creds.as_ref() {
-            Some((_, key)) => key.clone(),
-            None => return Ok(CloudModelsResponse { models: vec![] }),
+    // Check if logged in and get a still-valid token in a separate scope to release the lock
+    let access_token = {
+        let store = get_cloud_session_store();
+        let session = store.lock().unwrap();
+        match session.as_ref() {
+            Some(session) if !session.is_expired() => session.access_token.clone(),
+            // Expired or missing: force the caller back through login rather
+            // than silently serving the demo model list below.
+            _ => return Ok(CloudModelsResponse { models: vec![] }),
         }
     };
 
     // Try to fetch cloud models
     let client = reqwest::Client::new();
     let res = client.get("https://api.ollama.com/v1/models")
-        .header("Authorization", format!("Bearer {}", api_key))
+        .header("Authorization", format!("Bearer {}", access_token))
         .send()
         .await;
 
@@ -664,15 +2348,99 @@ pub fn shell(options: LeptosOptions) -> impl IntoView {
     }
 }
 
+/// Resolves after `ms` milliseconds - used to space out device-code polling
+/// without blocking the UI thread.
+#[cfg(target_arch = "wasm32")]
+async fn wasm_sleep_ms(ms: i32) {
+    let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+        if let Some(window) = web_sys::window() {
+            let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms);
+        }
+    });
+    let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
+
+/// Signals the route-sync components below drive when their path matches -
+/// shared via context rather than props since `<Routes>` children are mounted
+/// by `leptos_router`, not called directly from `App`'s view.
+#[derive(Clone, Copy)]
+struct RouteSignals {
+    set_models_panel_open: WriteSignal<bool>,
+    set_status_dropdown_open: WriteSignal<bool>,
+    set_deep_link_conversation_id: WriteSignal<Option<String>>,
+}
+
+/// Opens the model-management panel when `/models` is visited directly or
+/// restored from the Android back stack.
+#[component]
+fn ModelsRoute() -> impl IntoView {
+    if let Some(signals) = use_context::<RouteSignals>() {
+        signals.set_models_panel_open.set(true);
+    }
+}
+
+/// Opens the settings (status) dropdown when `/settings` is visited directly.
+#[component]
+fn SettingsRoute() -> impl IntoView {
+    if let Some(signals) = use_context::<RouteSignals>() {
+        signals.set_status_dropdown_open.set(true);
+    }
+}
+
+/// Captures the `:id` path param for a deep-linked conversation. The app only
+/// keeps one active chat history today, so this doesn't yet switch between
+/// stored conversations - it records the id so a future multi-conversation
+/// store has somewhere to read the requested id from.
+#[component]
+fn ChatRoute() -> impl IntoView {
+    let params = use_params_map();
+    if let Some(signals) = use_context::<RouteSignals>() {
+        signals.set_deep_link_conversation_id.set(params.get_untracked().get("id"));
+    }
+}
+
 #[component]
 pub fn App() -> impl IntoView {
     provide_meta_context();
 
     // State
     let (input, set_input) = signal(String::new());
-    let (messages, set_messages) = signal(Vec::<ChatMessage>::new());
+    // Each message gets its own `RwSignal` so a streaming AI bubble's `<For>`
+    // row stays mounted (keyed on the stable `id`) while its `text` is
+    // appended in place, instead of tearing down and rebuilding on every token.
+    let (messages, set_messages) = signal(Vec::<RwSignal<ChatMessage>>::new());
+    let (next_message_id, set_next_message_id) = signal(0u64);
+    let alloc_message_id = move || {
+        let id = next_message_id.get();
+        set_next_message_id.set(id + 1);
+        id
+    };
+    // Index of the message whose reaction picker is open, if any.
+    let (reaction_picker_open, set_reaction_picker_open) = signal::<Option<usize>>(None);
+
+    // In-conversation search overlay - `search_matches` holds the ids (not
+    // indices, since the list is filtered/reordered by nothing but still
+    // keyed on id) of messages whose text contains `search_query`, and
+    // `search_current` is the position within that list the user has
+    // navigated to.
+    let (search_open, set_search_open) = signal(false);
+    let (search_query, set_search_query) = signal(String::new());
+    let (search_matches, set_search_matches) = signal::<Vec<u64>>(vec![]);
+    let (search_current, set_search_current) = signal(0usize);
     let (selected_model, set_selected_model) = signal::<Option<String>>(None);
     let (is_streaming, set_is_streaming) = signal(false);
+    // Surfaced by the chat-stream fetch below when a request to Ollama
+    // fails outright; see error_template::OllamaRequestError.
+    let (chat_request_error, set_chat_request_error) = signal::<Option<OllamaRequestError>>(None);
+    let (reconnect_attempt, set_reconnect_attempt) = signal(0u32);
+    // The text/images of the last message sent, so the error banner's
+    // "Reconnect" button can resend without the user retyping anything.
+    let (last_chat_request, set_last_chat_request) = signal::<Option<(String, Vec<String>)>>(None);
+    // A generation left running in `resumable_generation` from before this
+    // page load - i.e. the WebView was reloaded or killed mid-stream. Shown
+    // as a banner offering to pick the request back up; see `do_send`'s
+    // persistence of this record and `resume_interrupted_generation` below.
+    let (interrupted_generation, set_interrupted_generation) = signal::<Option<ResumableGeneration>>(None);
     let (menu_open, set_menu_open) = signal(false);
     let (models_panel_open, set_models_panel_open) = signal(false);
     let (ollama_running, set_ollama_running) = signal(false);
@@ -681,15 +2449,106 @@ pub fn App() -> impl IntoView {
     let (new_model_name, set_new_model_name) = signal(String::new());
     let (active_downloads, set_active_downloads) = signal::<Vec<PullProgress>>(vec![]);
     let (deleting_model, set_deleting_model) = signal::<Option<String>>(None);
+    // Id captured from a `/chat/:id` deep link - see `ChatRoute`.
+    let (deep_link_conversation_id, set_deep_link_conversation_id) = signal::<Option<String>>(None);
     let (status_dropdown_open, set_status_dropdown_open) = signal(false);
-    let (current_theme, set_current_theme) = signal(String::from("light"));
 
-    // Brave Search state
-    let (brave_search_enabled, set_brave_search_enabled) = signal(false);
-    let (brave_api_token, set_brave_api_token) = signal(String::new());
-    let (brave_submenu_open, set_brave_submenu_open) = signal(false);
-    let (brave_test_status, set_brave_test_status) = signal::<Option<String>>(None);
-    let (brave_test_pending, set_brave_test_pending) = signal(false);
+    provide_context(RouteSignals {
+        set_models_panel_open,
+        set_status_dropdown_open,
+        set_deep_link_conversation_id,
+    });
+    // Pushed to whenever a panel the router also exposes as a path opens or
+    // closes, so the Android back button and restored deep links line up
+    // with what's actually on screen.
+    let navigate = use_navigate();
+
+    let (current_theme, set_current_theme) = signal(String::from("light"));
+    // The last built-in preset the user had selected - seeds the "Custom"
+    // color pickers on reset so they can start from e.g. Nordic and tweak.
+    let (last_preset_theme, set_last_preset_theme) = signal(String::from("light"));
+    let (custom_theme_colors, set_custom_theme_colors) = signal(CustomThemeColors::default());
+
+    // Read-aloud: id of the message currently being spoken (if any), the
+    // chosen voice/rate (persisted alongside the theme), and the voice names
+    // available from the browser's SpeechSynthesis API.
+    let (tts_speaking_id, set_tts_speaking_id) = signal::<Option<u64>>(None);
+    let (tts_voice, set_tts_voice) = signal(String::new());
+    let (tts_rate, set_tts_rate) = signal(1.0f64);
+    let (tts_voices, set_tts_voices) = signal::<Vec<String>>(vec![]);
+
+    // Base64 data-URL images staged for the next send, e.g. from the image
+    // picker below `#prompt-input`.
+    let (pending_images, set_pending_images) = signal::<Vec<String>>(vec![]);
+
+    // Full-screen media viewer, opened by tapping a message thumbnail - supports
+    // pinch-to-zoom and drag-to-pan via pointer events.
+    let (media_viewer_src, set_media_viewer_src) = signal::<Option<String>>(None);
+    let (media_viewer_scale, set_media_viewer_scale) = signal(1.0_f64);
+    let (media_viewer_offset, set_media_viewer_offset) = signal((0.0_f64, 0.0_f64));
+    let (media_viewer_pointers, set_media_viewer_pointers) = signal::<HashMap<i32, (f64, f64)>>(HashMap::new());
+    // (pointer position, offset) captured when a single-finger pan begins.
+    let (media_viewer_drag_anchor, set_media_viewer_drag_anchor) = signal::<Option<((f64, f64), (f64, f64))>>(None);
+    // (pointer distance, scale) captured when a two-finger pinch begins.
+    let (media_viewer_pinch_anchor, set_media_viewer_pinch_anchor) = signal::<Option<(f64, f64)>>(None);
+    // Guards the backdrop tap-to-close: a pan/pinch that ends over the
+    // backdrop should not also dismiss the modal.
+    let (media_viewer_moved, set_media_viewer_moved) = signal(false);
+
+    // Cross-tab sync: only the foreground tab drives long-poll/progress requests.
+    let (is_foreground, set_is_foreground) = signal(true);
+
+    // Unread background-completion counter, reflected in the document title
+    // and Web Notifications while the tab isn't focused; cleared on refocus.
+    let (pending_notifications, set_pending_notifications) = signal(0u32);
+    #[cfg(target_arch = "wasm32")]
+    let sync_channel = StoredValue::new(web_sys::BroadcastChannel::new("ollama-sync").ok());
+    let (num_ctx, set_num_ctx) = signal(4096u32);
+
+    // Per-model generation profiles (temperature, system prompt, stop tokens, ...)
+    let (model_profiles, set_model_profiles) = signal::<Vec<ModelProfile>>(vec![]);
+    let (active_profile_name, set_active_profile_name) = signal::<Option<String>>(None);
+    let (show_save_profile, set_show_save_profile) = signal(false);
+    let (new_profile_name, set_new_profile_name) = signal(String::new());
+
+    // Web search provider state - credentials keyed "{provider_key}.{field_key}"
+    // so the submenu can render whatever fields the active provider declares.
+    let (search_enabled, set_search_enabled) = signal(false);
+    let (search_provider, set_search_provider) = signal("brave".to_string());
+    let (search_credentials, set_search_credentials) = signal::<HashMap<String, String>>(HashMap::new());
+    let (search_submenu_open, set_search_submenu_open) = signal(false);
+    let (search_test_status, set_search_test_status) = signal::<Option<String>>(None);
+    let (search_test_pending, set_search_test_pending) = signal(false);
+
+    // OpenAI-compatible runner state
+    let (openai_panel_open, set_openai_panel_open) = signal(false);
+    let (openai_base_url, set_openai_base_url) = signal(String::new());
+    let (openai_api_key, set_openai_api_key) = signal(String::new());
+    let (openai_models, set_openai_models) = signal::<Vec<String>>(vec![]);
+    let (openai_models_error, set_openai_models_error) = signal::<Option<String>>(None);
+
+    // Registered remote Ollama server runners - each gets its own runner-item,
+    // model list, and add/pull/delete flow scoped to its base_url.
+    let (remote_servers, set_remote_servers) = signal::<Vec<RemoteServer>>(vec![]);
+    let (remote_models, set_remote_models) = signal::<HashMap<String, Vec<String>>>(HashMap::new());
+    let (remote_panel_open, set_remote_panel_open) = signal::<Option<String>>(None);
+    let (show_add_remote, set_show_add_remote) = signal(false);
+    let (new_remote_name, set_new_remote_name) = signal(String::new());
+    let (new_remote_url, set_new_remote_url) = signal(String::new());
+    let (show_add_remote_model, set_show_add_remote_model) = signal::<Option<String>>(None);
+    let (new_remote_model_name, set_new_remote_model_name) = signal(String::new());
+    let (deleting_remote_model, set_deleting_remote_model) = signal::<Option<String>>(None);
+
+    // Pending model deletion awaiting confirmation in the delete-confirm modal
+    let (confirm_delete, set_confirm_delete) = signal::<Option<PendingDelete>>(None);
+
+    // In-app model catalog browser - replaces the external "Browse Models" link
+    let (show_catalog, set_show_catalog) = signal::<Option<CatalogTarget>>(None);
+    let (catalog_search, set_catalog_search) = signal(String::new());
+
+    // Token-filtered search over the local and cloud model dropdowns
+    let (local_model_filter, set_local_model_filter) = signal(String::new());
+    let (cloud_model_filter, set_cloud_model_filter) = signal(String::new());
 
     // Cloud state
     let (cloud_panel_open, set_cloud_panel_open) = signal(false);
@@ -703,6 +2562,16 @@ pub fn App() -> impl IntoView {
     let (show_add_cloud_model, set_show_add_cloud_model) = signal(false);
     let (new_cloud_model_name, set_new_cloud_model_name) = signal(String::new());
 
+    // Device-code (RFC 8628) OAuth sign-in - polled in the background once
+    // `device_authorization` is populated, instead of a redirect-URI flow.
+    let (device_authorization, set_device_authorization) = signal::<Option<DeviceAuthorization>>(None);
+    let (device_auth_provider, set_device_auth_provider) = signal::<Option<String>>(None);
+    let (device_auth_copied, set_device_auth_copied) = signal(false);
+    // Set when an OAuth sign-in fails because the email is already linked to
+    // a different provider: (provider that already owns the email, provider
+    // the user just tried to sign in with).
+    let (account_link_prompt, set_account_link_prompt) = signal::<Option<(String, String)>>(None);
+
     // Load theme and Brave Search settings from localStorage on mount
     #[cfg(target_arch = "wasm32")]
     {
@@ -710,21 +2579,75 @@ pub fn App() -> impl IntoView {
         Effect::new(move |_| {
             if let Some(window) = web_sys::window() {
                 if let Ok(Some(storage)) = window.local_storage() {
+                    // Load the custom theme palette before applying the theme,
+                    // so a saved "custom" selection has colors to draw from.
+                    if let Ok(Some(saved_colors)) = storage.get_item("custom_theme_colors") {
+                        if let Ok(colors) = serde_json::from_str::<CustomThemeColors>(&saved_colors) {
+                            set_custom_theme_colors.set(colors);
+                        }
+                    }
+                    if let Ok(Some(saved_preset)) = storage.get_item("last_preset_theme") {
+                        set_last_preset_theme.set(saved_preset);
+                    }
                     // Load theme
                     if let Ok(Some(saved_theme)) = storage.get_item("theme") {
                         set_current_theme.set(saved_theme.clone());
                         if let Some(document) = window.document() {
                             if let Some(body) = document.body() {
                                 let _ = body.set_attribute("data-theme", &saved_theme);
+                                if saved_theme == "custom" {
+                                    let colors = custom_theme_colors.get_untracked();
+                                    for (var_name, getter) in CUSTOM_THEME_VARS {
+                                        let _ = body.style().set_property(var_name, getter(&colors));
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    // Load read-aloud voice/rate settings
+                    if let Ok(Some(voice)) = storage.get_item("tts_voice") {
+                        set_tts_voice.set(voice);
+                    }
+                    if let Ok(Some(rate)) = storage.get_item("tts_rate") {
+                        if let Ok(rate) = rate.parse::<f64>() {
+                            set_tts_rate.set(rate);
+                        }
+                    }
+                    // Load web search provider settings. Installs from before
+                    // search was generalized into `SearchProviderId` only
+                    // ever had Brave, under "brave_search_enabled"/
+                    // "brave_api_token" - if the new keys are absent, seed
+                    // them from the old ones so upgrading doesn't silently
+                    // drop an already-configured search setup.
+                    if let Ok(Some(enabled)) = storage.get_item("search_enabled") {
+                        set_search_enabled.set(enabled == "true");
+                    } else if let Ok(Some(legacy_enabled)) = storage.get_item("brave_search_enabled") {
+                        set_search_enabled.set(legacy_enabled == "true");
+                    }
+                    if let Ok(Some(provider)) = storage.get_item("search_provider") {
+                        set_search_provider.set(provider);
+                    }
+                    let mut loaded_credentials = HashMap::new();
+                    for provider in SearchProviderId::ALL {
+                        for (field_key, _, _) in provider.credential_fields() {
+                            let storage_key = format!("search_cred.{}.{}", provider.key(), field_key);
+                            if let Ok(Some(value)) = storage.get_item(&storage_key) {
+                                loaded_credentials.insert(format!("{}.{}", provider.key(), field_key), value);
                             }
                         }
                     }
-                    // Load Brave Search settings
-                    if let Ok(Some(enabled)) = storage.get_item("brave_search_enabled") {
-                        set_brave_search_enabled.set(enabled == "true");
+                    if !loaded_credentials.contains_key("brave.api_token") {
+                        if let Ok(Some(legacy_token)) = storage.get_item("brave_api_token") {
+                            loaded_credentials.insert("brave.api_token".to_string(), legacy_token);
+                        }
+                    }
+                    set_search_credentials.set(loaded_credentials);
+                    // Load OpenAI-compatible runner settings
+                    if let Ok(Some(base_url)) = storage.get_item("openai_base_url") {
+                        set_openai_base_url.set(base_url);
                     }
-                    if let Ok(Some(token)) = storage.get_item("brave_api_token") {
-                        set_brave_api_token.set(token);
+                    if let Ok(Some(api_key)) = storage.get_item("openai_api_key") {
+                        set_openai_api_key.set(api_key);
                     }
                     // Load last selected model
                     if let Ok(Some(saved_model)) = storage.get_item("selected_model") {
@@ -732,27 +2655,384 @@ pub fn App() -> impl IntoView {
                             set_selected_model.set(Some(saved_model));
                         }
                     }
+                    // Restore the chat history, reactions included, from the last session
+                    if let Ok(Some(saved_history)) = storage.get_item("chat_history") {
+                        if let Ok(mut history) = serde_json::from_str::<Vec<ChatMessage>>(&saved_history) {
+                            // History saved before per-message ids shipped all
+                            // deserialize with `id: 0` via `#[serde(default)]` -
+                            // reassign distinct ids here instead of trusting
+                            // that default, or the keyed `<For>` list below
+                            // collapses every legacy message onto one DOM node.
+                            let mut seen_ids = std::collections::HashSet::new();
+                            if history.iter().any(|m| !seen_ids.insert(m.id)) {
+                                for (i, msg) in history.iter_mut().enumerate() {
+                                    msg.id = i as u64;
+                                }
+                            }
+                            let next_id = history.iter().map(|m| m.id).max().map(|id| id + 1).unwrap_or(0);
+                            set_next_message_id.set(next_id);
+                            set_messages.set(history.into_iter().map(RwSignal::new).collect());
+                        }
+                    }
+                    // A record here means the last page load died mid-stream
+                    // (reload, Android killing the WebView, etc.) before it
+                    // could be cleared - offer to pick it back up.
+                    if let Ok(Some(saved_resumable)) = storage.get_item(RESUMABLE_GENERATION_KEY) {
+                        if let Ok(record) = serde_json::from_str::<ResumableGeneration>(&saved_resumable) {
+                            set_interrupted_generation.set(Some(record));
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    // Persist the chat history (including reactions) on every change so it
+    // survives a reload.
+    #[cfg(target_arch = "wasm32")]
+    Effect::new(move |_| {
+        let history: Vec<ChatMessage> = messages.get().iter().map(|m| m.get()).collect();
+        if let Some(window) = web_sys::window() {
+            if let Ok(Some(storage)) = window.local_storage() {
+                if let Ok(serialized) = serde_json::to_string(&history) {
+                    let _ = storage.set_item("chat_history", &serialized);
+                }
+            }
+        }
+    });
+
+    // Apply incoming cross-tab sync messages to our own signals, and track
+    // foreground/away status so only the visible tab drives polling.
+    #[cfg(target_arch = "wasm32")]
+    {
+        use wasm_bindgen::prelude::*;
+        use wasm_bindgen::JsCast;
+
+        sync_channel.with_value(|channel| {
+            if let Some(channel) = channel {
+                let cb = Closure::wrap(Box::new(move |ev: web_sys::MessageEvent| {
+                    let Some(data) = ev.data().as_string() else { return };
+                    let Ok(json) = serde_json::from_str::<serde_json::Value>(&data) else { return };
+                    match json["type"].as_str().unwrap_or("") {
+                        "select_model" => {
+                            if let Some(model) = json["model"].as_str() {
+                                set_selected_model.set(Some(model.to_string()));
+                            }
+                        }
+                        "active_downloads" => {
+                            if let Ok(downloads) = serde_json::from_value::<Vec<PullProgress>>(json["downloads"].clone()) {
+                                set_active_downloads.set(downloads);
+                            }
+                        }
+                        "streaming" => {
+                            if let Some(active) = json["active"].as_bool() {
+                                set_is_streaming.set(active);
+                            }
+                        }
+                        _ => {}
+                    }
+                }) as Box<dyn FnMut(_)>);
+                channel.set_onmessage(Some(cb.as_ref().unchecked_ref()));
+                cb.forget();
+            }
+        });
+
+        if let Some(document) = web_sys::window().and_then(|w| w.document()) {
+            let doc_for_listener = document.clone();
+            let cb = Closure::wrap(Box::new(move || {
+                set_is_foreground.set(!doc_for_listener.hidden());
+            }) as Box<dyn Fn()>);
+            let _ = document.add_event_listener_with_callback("visibilitychange", cb.as_ref().unchecked_ref());
+            cb.forget();
+        }
+    }
+
+    // Broadcasts a sync message to other tabs over the `ollama-sync` channel.
+    #[cfg(target_arch = "wasm32")]
+    let broadcast_sync = move |msg: serde_json::Value| {
+        sync_channel.with_value(|channel| {
+            if let Some(channel) = channel {
+                let _ = channel.post_message(&JsValue::from_str(&msg.to_string()));
+            }
+        });
+    };
+
+    // Fires a Web Notification when permission has already been granted, and
+    // requests it otherwise; the unread counter/title still surface the
+    // update either way, so a denied/undecided permission is a silent no-op.
+    #[cfg(target_arch = "wasm32")]
+    let notify = move |title: &str, body: &str| {
+        match web_sys::Notification::permission() {
+            web_sys::NotificationPermission::Granted => {
+                let opts = web_sys::NotificationOptions::new();
+                opts.set_body(body);
+                let _ = web_sys::Notification::new_with_options(title, &opts);
+            }
+            web_sys::NotificationPermission::Default => {
+                let _ = web_sys::Notification::request_permission();
+            }
+            _ => {}
+        }
+    };
+
+    // Clear the unread counter once the tab is back in focus.
+    Effect::new(move |_| {
+        if is_foreground.get() {
+            set_pending_notifications.set(0);
+        }
+    });
+
+    // The unread counter, active model, and stream state are all folded into
+    // the reactive `<Title>` below instead of imperative `document.set_title`.
+
+    // Remember the last deep-linked conversation id so a future
+    // multi-conversation store has somewhere to resume from after reload.
+    #[cfg(target_arch = "wasm32")]
+    Effect::new(move |_| {
+        if let Some(id) = deep_link_conversation_id.get() {
+            if let Some(window) = web_sys::window() {
+                if let Ok(Some(storage)) = window.local_storage() {
+                    let _ = storage.set_item("last_deep_link_conversation_id", &id);
+                }
+            }
+        }
+    });
+
+    // Load the per-model context window override whenever the selected model changes
+    #[cfg(target_arch = "wasm32")]
+    Effect::new(move |_| {
+        let Some(model) = selected_model.get() else { return };
+        if let Some(window) = web_sys::window() {
+            if let Ok(Some(storage)) = window.local_storage() {
+                let saved = storage.get_item(&format!("num_ctx_{}", model)).ok().flatten()
+                    .and_then(|v| v.parse::<u32>().ok());
+                set_num_ctx.set(saved.unwrap_or(4096));
+            }
+        }
+    });
+
+    // Load the saved generation profiles whenever the selected model changes
+    Effect::new(move |_| {
+        let Some(model) = selected_model.get() else { return };
+        set_active_profile_name.set(None);
+        spawn_local(async move {
+            if let Ok(profiles) = list_model_profiles(model).await {
+                set_model_profiles.set(profiles);
+            }
+        });
+    });
+
+    // Persist a num_ctx override for the currently selected model
+    let set_num_ctx_override = move |value: u32| {
+        set_num_ctx.set(value);
+        #[cfg(target_arch = "wasm32")]
+        {
+            if let Some(model) = selected_model.get() {
+                if let Some(window) = web_sys::window() {
+                    if let Ok(Some(storage)) = window.local_storage() {
+                        let _ = storage.set_item(&format!("num_ctx_{}", model), &value.to_string());
+                    }
+                }
+            }
+        }
+    };
+
+    // Apply theme change - for "custom" this also pushes the saved palette as
+    // inline `--*` variable overrides; for a built-in preset it clears any
+    // leftover overrides and remembers the preset so "Custom" can reset to it.
+    let apply_theme = move |theme: String| {
+        set_current_theme.set(theme.clone());
+        if theme != "custom" {
+            set_last_preset_theme.set(theme.clone());
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            if let Some(window) = web_sys::window() {
+                if let Ok(Some(storage)) = window.local_storage() {
+                    let _ = storage.set_item("theme", &theme);
+                    if theme != "custom" {
+                        let _ = storage.set_item("last_preset_theme", &theme);
+                    }
+                }
+                if let Some(document) = window.document() {
+                    if let Some(body) = document.body() {
+                        let _ = body.set_attribute("data-theme", &theme);
+                        if theme == "custom" {
+                            let colors = custom_theme_colors.get_untracked();
+                            for (var_name, getter) in CUSTOM_THEME_VARS {
+                                let _ = body.style().set_property(var_name, getter(&colors));
+                            }
+                        } else {
+                            for (var_name, _) in CUSTOM_THEME_VARS {
+                                let _ = body.style().remove_property(var_name);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    // Apply one custom-theme color, persisting the whole palette
+    let apply_custom_color = move |field: &'static str, value: String| {
+        let mut colors = custom_theme_colors.get();
+        match field {
+            "background" => colors.background = value,
+            "surface" => colors.surface = value,
+            "accent" => colors.accent = value,
+            "text" => colors.text = value,
+            "user_bubble" => colors.user_bubble = value,
+            "ai_bubble" => colors.ai_bubble = value,
+            _ => {}
+        }
+        set_custom_theme_colors.set(colors.clone());
+        #[cfg(target_arch = "wasm32")]
+        {
+            if let Some(window) = web_sys::window() {
+                if let Ok(Some(storage)) = window.local_storage() {
+                    if let Ok(serialized) = serde_json::to_string(&colors) {
+                        let _ = storage.set_item("custom_theme_colors", &serialized);
+                    }
+                }
+                if let Some(document) = window.document() {
+                    if let Some(body) = document.body() {
+                        for (var_name, getter) in CUSTOM_THEME_VARS {
+                            let _ = body.style().set_property(var_name, getter(&colors));
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    // Reset the custom palette to whichever built-in preset was last active
+    let reset_custom_to_preset = move || {
+        let colors = preset_theme_colors(&last_preset_theme.get());
+        set_custom_theme_colors.set(colors.clone());
+        #[cfg(target_arch = "wasm32")]
+        {
+            if let Some(window) = web_sys::window() {
+                if let Ok(Some(storage)) = window.local_storage() {
+                    if let Ok(serialized) = serde_json::to_string(&colors) {
+                        let _ = storage.set_item("custom_theme_colors", &serialized);
+                    }
+                }
+                if let Some(document) = window.document() {
+                    if let Some(body) = document.body() {
+                        for (var_name, getter) in CUSTOM_THEME_VARS {
+                            let _ = body.style().set_property(var_name, getter(&colors));
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    // Apply read-aloud voice/rate, persisted alongside the theme
+    let apply_tts_voice = move |voice: String| {
+        set_tts_voice.set(voice.clone());
+        #[cfg(target_arch = "wasm32")]
+        {
+            if let Some(window) = web_sys::window() {
+                if let Ok(Some(storage)) = window.local_storage() {
+                    let _ = storage.set_item("tts_voice", &voice);
+                }
+            }
+        }
+    };
+    let apply_tts_rate = move |rate: f64| {
+        set_tts_rate.set(rate);
+        #[cfg(target_arch = "wasm32")]
+        {
+            if let Some(window) = web_sys::window() {
+                if let Ok(Some(storage)) = window.local_storage() {
+                    let _ = storage.set_item("tts_rate", &rate.to_string());
+                }
+            }
+        }
+    };
+
+    // Populate the list of available speech-synthesis voices - browsers load
+    // this asynchronously, so also listen for `voiceschanged`.
+    #[cfg(target_arch = "wasm32")]
+    {
+        use wasm_bindgen::prelude::*;
+        use wasm_bindgen::JsCast;
+        let load_voices = move || {
+            if let Some(window) = web_sys::window() {
+                if let Ok(synth) = window.speech_synthesis() {
+                    let names: Vec<String> = synth.get_voices().into_iter().map(|v| v.name()).collect();
+                    if !names.is_empty() {
+                        set_tts_voices.set(names);
+                    }
                 }
             }
-        });
+        };
+        load_voices();
+        if let Some(window) = web_sys::window() {
+            if let Ok(synth) = window.speech_synthesis() {
+                let cb = Closure::wrap(Box::new(load_voices) as Box<dyn Fn()>);
+                synth.set_onvoiceschanged(Some(cb.as_ref().unchecked_ref()));
+                cb.forget();
+            }
+        }
     }
 
-    // Apply theme change
-    let apply_theme = move |theme: String| {
-        set_current_theme.set(theme.clone());
+    // Stop any in-progress read-aloud as soon as a new response starts streaming.
+    #[cfg(target_arch = "wasm32")]
+    Effect::new(move |_| {
+        if is_streaming.get() {
+            if let Some(window) = web_sys::window() {
+                if let Ok(synth) = window.speech_synthesis() {
+                    synth.cancel();
+                }
+            }
+            set_tts_speaking_id.set(None);
+        }
+    });
+
+    // Read a message aloud via the browser's SpeechSynthesis API, or stop if
+    // it's already the one speaking.
+    let toggle_speak = move |id: u64, text: String| {
         #[cfg(target_arch = "wasm32")]
         {
+            use wasm_bindgen::prelude::*;
+            use wasm_bindgen::JsCast;
             if let Some(window) = web_sys::window() {
-                if let Ok(Some(storage)) = window.local_storage() {
-                    let _ = storage.set_item("theme", &theme);
-                }
-                if let Some(document) = window.document() {
-                    if let Some(body) = document.body() {
-                        let _ = body.set_attribute("data-theme", &theme);
+                if let Ok(synth) = window.speech_synthesis() {
+                    if tts_speaking_id.get() == Some(id) {
+                        synth.cancel();
+                        set_tts_speaking_id.set(None);
+                        return;
+                    }
+                    synth.cancel();
+                    let plain = strip_markdown_for_speech(&text);
+                    if let Ok(utterance) = web_sys::SpeechSynthesisUtterance::new_with_text(&plain) {
+                        utterance.set_rate(tts_rate.get() as f32);
+                        let voice_name = tts_voice.get();
+                        if !voice_name.is_empty() {
+                            for voice in synth.get_voices() {
+                                if voice.name() == voice_name {
+                                    utterance.set_voice(Some(&voice));
+                                    break;
+                                }
+                            }
+                        }
+                        let cb = Closure::wrap(Box::new(move || {
+                            set_tts_speaking_id.set(None);
+                        }) as Box<dyn FnMut()>);
+                        utterance.set_onend(Some(cb.as_ref().unchecked_ref()));
+                        cb.forget();
+                        synth.speak(&utterance);
+                        set_tts_speaking_id.set(Some(id));
                     }
                 }
             }
         }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let _ = (id, text);
+        }
     };
 
     // Resources
@@ -769,6 +3049,8 @@ pub fn App() -> impl IntoView {
             }
         }
     );
+    let remote_servers_resource = Resource::new(|| (), |_| list_remote_servers());
+    let catalog_resource = Resource::new(|| (), |_| list_model_catalog());
 
     // Toggle action
     let toggle_action = Action::new(move |_: &()| async move {
@@ -820,10 +3102,16 @@ pub fn App() -> impl IntoView {
                 done: false,
                 error: None,
                 bytes_downloaded: 0,
+                total_bytes: 0,
                 speed: "".to_string(),
+                eta: "".to_string(),
+                speed_bps: 0.0,
                 last_update: 0,
+                last_sample_ms: 0,
             });
         });
+        #[cfg(target_arch = "wasm32")]
+        broadcast_sync(serde_json::json!({ "type": "active_downloads", "downloads": active_downloads.get() }));
 
         // Start the pull
         let model = model_name.trim().to_string();
@@ -836,11 +3124,137 @@ pub fn App() -> impl IntoView {
         set_show_add_model.set(false);
     };
 
-    // Poll for download progress
+    // Track download progress via a long-poll event bus, falling back to the
+    // old fixed-interval polling only once the long-poll connection has
+    // errored out repeatedly.
     #[cfg(target_arch = "wasm32")]
     {
         use wasm_bindgen::prelude::*;
+        use wasm_bindgen::JsCast;
+
+        let (last_event_id, set_last_event_id) = signal(0u64);
+        let (long_poll_running, set_long_poll_running) = signal(false);
+        let (long_poll_errors, set_long_poll_errors) = signal(0u32);
+        const MAX_LONG_POLL_ERRORS: u32 = 3;
+
+        let apply_event = move |event: &JsValue| {
+            let get_str = |key: &str| -> Option<String> {
+                js_sys::Reflect::get(event, &JsValue::from_str(key)).ok().and_then(|v| v.as_string())
+            };
+            let get_f64 = |key: &str| -> f64 {
+                js_sys::Reflect::get(event, &JsValue::from_str(key)).ok().and_then(|v| v.as_f64()).unwrap_or(0.0)
+            };
+            let get_bool = |key: &str| -> bool {
+                js_sys::Reflect::get(event, &JsValue::from_str(key)).ok().and_then(|v| v.as_bool()).unwrap_or(false)
+            };
+
+            let model = get_str("model").unwrap_or_default();
+            let done = get_bool("done");
+            let error = get_str("error");
+            let is_complete = done && error.is_none();
+            let was_done = active_downloads.get().iter().any(|d| d.model == model && d.done);
+
+            set_active_downloads.update(|downloads| {
+                if let Some(d) = downloads.iter_mut().find(|d| d.model == model) {
+                    d.status = get_str("status").unwrap_or_default();
+                    d.percent = get_f64("percent") as f32;
+                    d.done = done;
+                    d.error = error;
+                    d.bytes_downloaded = get_f64("bytes_downloaded") as u64;
+                    d.total_bytes = get_f64("total") as u64;
+                    d.speed = get_str("speed").unwrap_or_default();
+                    d.eta = get_str("eta").unwrap_or_default();
+                    d.last_update = (js_sys::Date::now() / 1000.0) as i64;
+                }
+            });
+            broadcast_sync(serde_json::json!({ "type": "active_downloads", "downloads": active_downloads.get() }));
+
+            if is_complete {
+                status_resource.refetch();
+                if !was_done && !is_foreground.get() {
+                    set_pending_notifications.update(|n| *n += 1);
+                    notify("Download complete", &format!("{} finished downloading", model));
+                }
+            }
+        };
+
+        let start_long_poll = move || {
+            spawn_local(async move {
+                loop {
+                    let pending: Vec<String> = active_downloads.get().iter()
+                        .filter(|d| !d.done)
+                        .map(|d| d.model.clone())
+                        .collect();
+                    if pending.is_empty() {
+                        set_long_poll_running.set(false);
+                        return;
+                    }
+
+                    let window = web_sys::window().unwrap();
+                    let opts = web_sys::RequestInit::new();
+                    opts.set_method("POST");
+                    opts.set_body(&JsValue::from_str(&serde_json::json!({
+                        "models": pending,
+                        "last_id": last_event_id.get()
+                    }).to_string()));
+                    let headers = web_sys::Headers::new().unwrap();
+                    headers.set("Content-Type", "application/json").unwrap();
+                    opts.set_headers(&headers);
+
+                    let request = web_sys::Request::new_with_str_and_init("/api/events", &opts).unwrap();
+                    let resp = wasm_bindgen_futures::JsFuture::from(window.fetch_with_request(&request)).await;
+
+                    let batch = resp.ok()
+                        .and_then(|r| r.dyn_into::<web_sys::Response>().ok())
+                        .and_then(|r| r.json().ok())
+                        .map(wasm_bindgen_futures::JsFuture::from);
+
+                    let Some(batch) = batch else {
+                        set_long_poll_errors.update(|n| *n += 1);
+                        if long_poll_errors.get() >= MAX_LONG_POLL_ERRORS {
+                            set_long_poll_running.set(false);
+                            return;
+                        }
+                        continue;
+                    };
+
+                    match batch.await {
+                        Ok(json) => {
+                            set_long_poll_errors.set(0);
+                            if let Some(id) = js_sys::Reflect::get(&json, &JsValue::from_str("last_id")).ok().and_then(|v| v.as_f64()) {
+                                set_last_event_id.set(id as u64);
+                            }
+                            if let Ok(events_val) = js_sys::Reflect::get(&json, &JsValue::from_str("events")) {
+                                for event in js_sys::Array::from(&events_val).iter() {
+                                    apply_event(&event);
+                                }
+                            }
+                        }
+                        Err(_) => {
+                            set_long_poll_errors.update(|n| *n += 1);
+                            if long_poll_errors.get() >= MAX_LONG_POLL_ERRORS {
+                                set_long_poll_running.set(false);
+                                return;
+                            }
+                        }
+                    }
+                }
+            });
+        };
 
+        Effect::new(move |_| {
+            let downloads = active_downloads.get();
+            let should_poll = downloads.iter().any(|d| !d.done)
+                && long_poll_errors.get() < MAX_LONG_POLL_ERRORS
+                && is_foreground.get();
+            if should_poll && !long_poll_running.get() {
+                set_long_poll_running.set(true);
+                start_long_poll();
+            }
+        });
+
+        // Fallback: the old fixed-interval poll, only once long-polling has
+        // given up after repeated errors.
         let check_progress = move || {
             let downloads = active_downloads.get();
             let pending: Vec<_> = downloads.iter()
@@ -856,51 +3270,34 @@ pub fn App() -> impl IntoView {
 
                         set_active_downloads.update(|downloads| {
                             if let Some(d) = downloads.iter_mut().find(|d| d.model == model_clone) {
-                                // Calculate download speed
-                                let now = js_sys::Date::now() as i64;
-                                let time_diff = if d.last_update > 0 { (now - d.last_update) / 1000 } else { 0 };
-                                let percent_diff = progress.percent - d.percent;
-                                
-                                // Estimate speed based on percent change (rough estimate)
-                                let speed_str = if time_diff > 0 && percent_diff > 0.0 {
-                                    // Assume models are roughly 4GB for estimation
-                                    let estimated_bytes = (percent_diff / 100.0) * 4_000_000_000.0;
-                                    let bytes_per_sec = estimated_bytes / (time_diff as f32);
-                                    if bytes_per_sec > 1_000_000_000.0 {
-                                        format!("{:.1} GB/s", bytes_per_sec / 1_000_000_000.0)
-                                    } else if bytes_per_sec > 1_000_000.0 {
-                                        format!("{:.1} MB/s", bytes_per_sec / 1_000_000.0)
-                                    } else if bytes_per_sec > 1_000.0 {
-                                        format!("{:.1} KB/s", bytes_per_sec / 1_000.0)
-                                    } else {
-                                        format!("{:.0} B/s", bytes_per_sec)
-                                    }
-                                } else {
-                                    "".to_string()
-                                };
-
                                 d.status = progress.status;
                                 d.percent = progress.percent;
                                 d.done = progress.done;
                                 d.error = progress.error;
-                                d.speed = speed_str;
-                                d.last_update = now;
+                                d.bytes_downloaded = progress.bytes_downloaded;
+                                d.total_bytes = progress.total_bytes;
+                                d.speed = progress.speed;
+                                d.eta = progress.eta;
+                                d.last_update = (js_sys::Date::now() / 1000.0) as i64;
                             }
                         });
+                        broadcast_sync(serde_json::json!({ "type": "active_downloads", "downloads": active_downloads.get() }));
 
-                        // Refresh models list when complete
                         if is_complete {
                             status_resource.refetch();
+                            if !is_foreground.get() {
+                                set_pending_notifications.update(|n| *n += 1);
+                                notify("Download complete", &format!("{} finished downloading", model_clone));
+                            }
                         }
                     }
                 });
             }
         };
 
-        // Set up interval to check progress
         Effect::new(move |_| {
             let downloads = active_downloads.get();
-            if downloads.iter().any(|d| !d.done) {
+            if downloads.iter().any(|d| !d.done) && long_poll_errors.get() >= MAX_LONG_POLL_ERRORS && is_foreground.get() {
                 let cb = Closure::wrap(Box::new(move || {
                     check_progress();
                 }) as Box<dyn Fn()>);
@@ -908,7 +3305,7 @@ pub fn App() -> impl IntoView {
                 if let Some(window) = web_sys::window() {
                     let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(
                         cb.as_ref().unchecked_ref(),
-                        2000, // Check every 2 seconds
+                        2000,
                     );
                 }
                 cb.forget();
@@ -1005,19 +3402,131 @@ pub fn App() -> impl IntoView {
         });
     }
 
-    // OAuth login handler
-    let do_oauth_login = move |provider: String| {
+    // OAuth login handler - device-code grant (RFC 8628) instead of a
+    // redirect-URI flow, which doesn't survive an Android WebView reliably.
+    // Starts the authorization, then polls the token endpoint in a single
+    // background task until it resolves, expires, or is denied.
+    // `link_with` is `Some(attempted_provider)` when this run is re-authenticating
+    // against `provider` (the one that already owns the email) to link it with
+    // the identity the user originally tried - see `account_link_prompt`.
+    let do_oauth_login = move |provider: String, link_with: Option<String>| {
+        set_cloud_login_pending.set(true);
+        set_cloud_login_error.set(None);
+        set_account_link_prompt.set(None);
+        set_device_authorization.set(None);
+        set_device_auth_copied.set(false);
+        set_device_auth_provider.set(Some(provider.clone()));
+
+        #[cfg(target_arch = "wasm32")]
+        spawn_local(async move {
+            let authorization = match start_device_authorization(provider.clone()).await {
+                Ok(authorization) => authorization,
+                Err(e) => {
+                    // Nothing was provisioned server-side yet, so there's no partial
+                    // session to clean up - just reset the pending UI state.
+                    set_cloud_login_error.set(Some(format!("Error: {}", e)));
+                    set_cloud_login_pending.set(false);
+                    set_device_auth_provider.set(None);
+                    return;
+                }
+            };
+
+            set_device_authorization.set(Some(authorization.clone()));
+            set_cloud_login_pending.set(false);
+
+            let device_code = authorization.device_code.clone();
+            let deadline = (js_sys::Date::now() / 1000.0) as i64 + authorization.expires_in;
+            let mut interval_ms = (authorization.interval.max(1) * 1000) as i32;
+
+            loop {
+                wasm_sleep_ms(interval_ms).await;
+
+                if (js_sys::Date::now() / 1000.0) as i64 >= deadline {
+                    set_cloud_login_error.set(Some("The sign-in code expired - try again".to_string()));
+                    set_device_authorization.set(None);
+                    set_device_auth_provider.set(None);
+                    break;
+                }
+
+                match poll_device_authorization(provider.clone(), device_code.clone(), link_with.clone()).await {
+                    Ok(response) => match response.status.as_str() {
+                        "success" => {
+                            set_cloud_logged_in.set(true);
+                            set_cloud_user_email.set(response.email);
+                            set_show_email_login.set(false);
+                            set_device_authorization.set(None);
+                            set_device_auth_provider.set(None);
+                            cloud_models_resource.refetch();
+                            break;
+                        }
+                        "pending" => continue,
+                        "slow_down" => {
+                            interval_ms += 5000;
+                            continue;
+                        }
+                        "identity_exists" => {
+                            // A half-provisioned session is never created by `exchange_for_session`
+                            // on this path, but clear pending UI state before showing the prompt.
+                            set_device_authorization.set(None);
+                            set_device_auth_provider.set(None);
+                            if let Some(existing) = response.existing_provider {
+                                set_account_link_prompt.set(Some((existing, provider.clone())));
+                            } else {
+                                set_cloud_login_error.set(Some(response.message.unwrap_or_else(|| "Sign-in failed".to_string())));
+                            }
+                            break;
+                        }
+                        "expired" | "denied" => {
+                            set_cloud_login_error.set(Some(
+                                response.message.unwrap_or_else(|| "Sign-in was not completed".to_string())
+                            ));
+                            set_device_authorization.set(None);
+                            set_device_auth_provider.set(None);
+                            break;
+                        }
+                        _ => {
+                            set_cloud_login_error.set(Some(response.message.unwrap_or_else(|| "Sign-in failed".to_string())));
+                            set_device_authorization.set(None);
+                            set_device_auth_provider.set(None);
+                            break;
+                        }
+                    },
+                    Err(e) => {
+                        set_cloud_login_error.set(Some(format!("Error: {}", e)));
+                        set_device_authorization.set(None);
+                        set_device_auth_provider.set(None);
+                        break;
+                    }
+                }
+            }
+        });
+    };
+
+    // Email login handler
+    let do_email_login = move || {
+        let email = cloud_email.get();
+        let password = cloud_password.get();
+
+        if email.trim().is_empty() || password.trim().is_empty() {
+            set_cloud_login_error.set(Some("Please enter email and password".to_string()));
+            return;
+        }
+
         set_cloud_login_pending.set(true);
         set_cloud_login_error.set(None);
 
         spawn_local(async move {
-            match cloud_oauth_login(provider.clone()).await {
+            match cloud_email_login(email.clone(), password, None).await {
                 Ok(response) => {
                     if response.success {
                         set_cloud_logged_in.set(true);
-                        set_cloud_user_email.set(response.api_key);
+                        set_cloud_user_email.set(Some(email));
+                        set_cloud_email.set(String::new());
+                        set_cloud_password.set(String::new());
                         set_show_email_login.set(false);
                         cloud_models_resource.refetch();
+                    } else if let Some(existing) = response.existing_provider {
+                        set_account_link_prompt.set(Some((existing, "email".to_string())));
                     } else {
                         set_cloud_login_error.set(Some(response.message));
                     }
@@ -1030,13 +3539,17 @@ pub fn App() -> impl IntoView {
         });
     };
 
-    // Email login handler
-    let do_email_login = move || {
+    // Completes an account-link when the colliding identity is email/password
+    // rather than another OAuth provider - `do_oauth_login` can't re-run a
+    // device-code flow for "email" (there's no such provider endpoint), so
+    // this collects the password instead and links `link_with` (the OAuth
+    // provider the user originally tried) onto the email account.
+    let do_link_email_account = move |link_with: String| {
         let email = cloud_email.get();
         let password = cloud_password.get();
 
         if email.trim().is_empty() || password.trim().is_empty() {
-            set_cloud_login_error.set(Some("Please enter email and password".to_string()));
+            set_cloud_login_error.set(Some("Please enter the password for this email account".to_string()));
             return;
         }
 
@@ -1044,14 +3557,14 @@ pub fn App() -> impl IntoView {
         set_cloud_login_error.set(None);
 
         spawn_local(async move {
-            match cloud_email_login(email.clone(), password).await {
+            match cloud_email_login(email.clone(), password, Some(link_with)).await {
                 Ok(response) => {
                     if response.success {
                         set_cloud_logged_in.set(true);
                         set_cloud_user_email.set(Some(email));
                         set_cloud_email.set(String::new());
                         set_cloud_password.set(String::new());
-                        set_show_email_login.set(false);
+                        set_account_link_prompt.set(None);
                         cloud_models_resource.refetch();
                     } else {
                         set_cloud_login_error.set(Some(response.message));
@@ -1074,10 +3587,62 @@ pub fn App() -> impl IntoView {
         });
     };
 
+    // Periodically checks the cloud session's remaining lifetime and
+    // proactively refreshes it before it expires. A failed refresh clears
+    // the logged-in state and surfaces a re-login prompt instead of letting
+    // every subsequent cloud request silently 401.
+    #[cfg(target_arch = "wasm32")]
+    {
+        use wasm_bindgen::prelude::*;
+        use wasm_bindgen::JsCast;
+
+        const CLOUD_SESSION_CHECK_INTERVAL_MS: i32 = 60_000;
+        const CLOUD_SESSION_REFRESH_THRESHOLD_SECS: i64 = 300;
+
+        let (cloud_check_tick, set_cloud_check_tick) = signal(0u32);
+
+        Effect::new(move |_| {
+            cloud_check_tick.get();
+
+            if cloud_logged_in.get() {
+                spawn_local(async move {
+                    if let Ok(Some(remaining)) = cloud_session_lifetime().await {
+                        if remaining < CLOUD_SESSION_REFRESH_THRESHOLD_SECS {
+                            match refresh_cloud_session().await {
+                                Ok(response) if response.success => {}
+                                _ => {
+                                    set_cloud_logged_in.set(false);
+                                    set_cloud_user_email.set(None);
+                                    set_cloud_login_error.set(Some(
+                                        "Your Ollama Cloud session expired - please log in again.".to_string(),
+                                    ));
+                                }
+                            }
+                        }
+                    }
+                });
+            }
+
+            if let Some(window) = web_sys::window() {
+                let cb = Closure::once(Box::new(move || {
+                    set_cloud_check_tick.update(|n| *n += 1);
+                }) as Box<dyn FnOnce()>);
+                let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(
+                    cb.as_ref().unchecked_ref(),
+                    CLOUD_SESSION_CHECK_INTERVAL_MS,
+                );
+                cb.forget();
+            }
+        });
+    }
+
     // Auto-scroll chat window when messages change
     #[cfg(target_arch = "wasm32")]
     Effect::new(move |_| {
-        let _ = messages.get(); // Subscribe to messages changes
+        let msgs = messages.get(); // Subscribe to additions/removals
+        if let Some(last) = msgs.last() {
+            let _ = last.get().text.len(); // ...and to the streaming message's own text
+        }
         // Use requestAnimationFrame to ensure DOM is updated before scrolling
         if let Some(window) = web_sys::window() {
             use wasm_bindgen::prelude::*;
@@ -1096,6 +3661,71 @@ pub fn App() -> impl IntoView {
         }
     });
 
+    // Scroll the chat bubble for `id` into view - used by the search overlay
+    // to jump to the current match.
+    let scroll_to_message = move |id: u64| {
+        #[cfg(target_arch = "wasm32")]
+        {
+            use wasm_bindgen::prelude::*;
+            use wasm_bindgen::JsCast;
+            if let Some(window) = web_sys::window() {
+                let cb = Closure::once(Box::new(move || {
+                    if let Some(window) = web_sys::window() {
+                        if let Some(document) = window.document() {
+                            if let Some(el) = document.get_element_by_id(&format!("msg-{}", id)) {
+                                el.scroll_into_view();
+                            }
+                        }
+                    }
+                }) as Box<dyn FnOnce()>);
+                let _ = window.request_animation_frame(cb.as_ref().unchecked_ref());
+                cb.forget();
+            }
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let _ = id;
+        }
+    };
+
+    // Recompute the set of matching message ids whenever the query or the
+    // message list changes, and jump to the first match as soon as it appears.
+    Effect::new(move |_| {
+        let query = search_query.get().trim().to_lowercase();
+        if query.is_empty() {
+            set_search_matches.set(vec![]);
+            set_search_current.set(0);
+            return;
+        }
+        let matches: Vec<u64> = messages.get()
+            .into_iter()
+            .map(|m| m.get())
+            .filter(|m| m.text.to_lowercase().contains(&query))
+            .map(|m| m.id)
+            .collect();
+        set_search_current.set(0);
+        if let Some(first) = matches.first().copied() {
+            scroll_to_message(first);
+        }
+        set_search_matches.set(matches);
+    });
+
+    // Step to the next (delta=1) or previous (delta=-1) search match, wrapping
+    // around, and scroll the chat window to it.
+    let goto_search_match = move |delta: i32| {
+        let matches = search_matches.get();
+        if matches.is_empty() {
+            return;
+        }
+        let len = matches.len() as i32;
+        let current = search_current.get() as i32;
+        let next = ((current + delta) % len + len) % len;
+        set_search_current.set(next as usize);
+        if let Some(id) = matches.get(next as usize).copied() {
+            scroll_to_message(id);
+        }
+    };
+
     // Send message handler
     let do_send = move || {
         let text = input.get();
@@ -1103,144 +3733,604 @@ pub fn App() -> impl IntoView {
             return;
         }
 
+        // Snapshot prior turns for the chat history sent to Ollama
+        let history: Vec<(String, String)> = messages.get()
+            .into_iter()
+            .map(|m| m.get())
+            .map(|m| ((if m.role == "ai" { "assistant" } else { "user" }).to_string(), m.text))
+            .collect();
+
+        let images = pending_images.get();
+        set_chat_request_error.set(None);
+        set_reconnect_attempt.set(0);
+        set_last_chat_request.set(Some((text.clone(), images.clone())));
+        // A fresh send supersedes whatever the interrupted-generation banner
+        // was offering to resume.
+        set_interrupted_generation.set(None);
+        #[cfg(target_arch = "wasm32")]
+        clear_resumable_generation();
+
         // Add user message
         set_messages.update(|msgs| {
-            msgs.push(ChatMessage {
+            msgs.push(RwSignal::new(ChatMessage {
+                id: alloc_message_id(),
                 role: "user".to_string(),
                 text: text.clone(),
-            });
+                images: images.clone(),
+                reactions: HashMap::new(),
+            }));
         });
 
-        // Add placeholder AI message
+        // Add placeholder AI message - fills in place as tokens stream in, so
+        // the "thinking" animation and the final content share one stable row.
         set_messages.update(|msgs| {
-            msgs.push(ChatMessage {
+            msgs.push(RwSignal::new(ChatMessage {
+                id: alloc_message_id(),
                 role: "ai".to_string(),
                 text: "".to_string(),
-            });
+                images: vec![],
+                reactions: HashMap::new(),
+            }));
         });
 
         set_input.set(String::new());
+        set_pending_images.set(vec![]);
         set_is_streaming.set(true);
+        #[cfg(target_arch = "wasm32")]
+        broadcast_sync(serde_json::json!({ "type": "streaming", "active": true }));
 
         // Start streaming
         let model = selected_model.get().unwrap();
         let user_query = text.clone();
-        let search_enabled = brave_search_enabled.get();
-        let api_token = brave_api_token.get();
+        let web_search_enabled = search_enabled.get();
+        let active_search_provider = search_provider.get();
+        let active_search_credentials = search_credentials.get();
+        let provider_configured = !search_provider_missing_credentials(&active_search_provider, &active_search_credentials);
+        let ctx_window = num_ctx.get();
+        let openai_url = openai_base_url.get();
+        let openai_key = openai_api_key.get();
+        let remote_url = model.strip_prefix("remote:")
+            .and_then(|rest| rest.split_once(':'))
+            .and_then(|(server_id, _)| remote_servers.get().into_iter().find(|s| s.id == server_id))
+            .map(|s| s.base_url);
+        let active_profile = active_profile_name.get()
+            .and_then(|name| model_profiles.get().into_iter().find(|p| p.name == name));
 
         #[cfg(target_arch = "wasm32")]
         {
             use wasm_bindgen::prelude::*;
             use wasm_bindgen::JsCast;
 
+            const MAX_TOOL_ROUNDS: u32 = 3;
+
             // Use fetch with SSE
             wasm_bindgen_futures::spawn_local(async move {
                 let window = web_sys::window().unwrap();
 
-                // Build the prompt - optionally with search results
-                let prompt = if search_enabled && !api_token.trim().is_empty() {
-                    // First, perform web search
-                    match brave_search(user_query.clone(), api_token).await {
-                        Ok(search_response) if search_response.success && !search_response.results.is_empty() => {
-                            // Build context from search results
-                            let mut context = String::from("I searched the web for your question. Here are the relevant results:\n\n");
-                            for (i, result) in search_response.results.iter().enumerate() {
-                                context.push_str(&format!(
-                                    "{}. **{}**\n   URL: {}\n   {}\n\n",
-                                    i + 1,
-                                    result.title,
-                                    result.url,
-                                    result.description
-                                ));
+                let web_search_tool = serde_json::json!([{
+                    "type": "function",
+                    "function": {
+                        "name": "web_search",
+                        "description": "Search the web for up-to-date information",
+                        "parameters": {
+                            "type": "object",
+                            "properties": { "query": { "type": "string" } },
+                            "required": ["query"]
+                        }
+                    }
+                }]);
+                let tools_enabled = web_search_enabled && provider_configured;
+
+                let mut chat_messages: Vec<serde_json::Value> = history.iter()
+                    .map(|(role, content)| serde_json::json!({ "role": role, "content": content }))
+                    .collect();
+                if let Some(profile) = &active_profile {
+                    if !profile.system_prompt.trim().is_empty() {
+                        chat_messages.insert(0, serde_json::json!({ "role": "system", "content": profile.system_prompt }));
+                    }
+                }
+                let mut user_message = serde_json::json!({ "role": "user", "content": user_query });
+                if !images.is_empty() {
+                    user_message["images"] = serde_json::Value::from(images.clone());
+                }
+                chat_messages.push(user_message);
+
+                let mut full_text = String::new();
+                let ai_message_id = messages.get_untracked().last().map(|m| m.get_untracked().id);
+                let mut resumable_history = history.clone();
+                resumable_history.push(("user".to_string(), user_query.clone()));
+
+                for round in 0..MAX_TOOL_ROUNDS {
+                    // A saved profile's sampling parameters take over from the
+                    // bare num_ctx override when one is active for this model.
+                    let mut options = serde_json::json!({ "num_ctx": ctx_window });
+                    if let Some(profile) = &active_profile {
+                        options["num_ctx"] = serde_json::Value::from(profile.num_ctx);
+                        options["temperature"] = serde_json::Value::from(profile.temperature);
+                        options["top_p"] = serde_json::Value::from(profile.top_p);
+                        options["repeat_penalty"] = serde_json::Value::from(profile.repeat_penalty);
+                        if !profile.stop.is_empty() {
+                            options["stop"] = serde_json::Value::from(profile.stop.clone());
+                        }
+                    }
+
+                    let mut body = serde_json::json!({
+                        "model": model,
+                        "messages": chat_messages,
+                        "options": options
+                    });
+                    if model.starts_with("openai:") {
+                        body["openai_base_url"] = serde_json::Value::from(openai_url.clone());
+                        body["openai_api_key"] = serde_json::Value::from(openai_key.clone());
+                    }
+                    if let Some(base_url) = &remote_url {
+                        body["remote_base_url"] = serde_json::Value::from(base_url.clone());
+                    }
+                    if tools_enabled {
+                        body["tools"] = web_search_tool.clone();
+                    }
+
+                    // A failed attempt is retried in place (with backoff) when it looks
+                    // transient; a configuration error or an exhausted retry budget
+                    // surfaces through `chat_request_error` instead of looping forever.
+                    const MAX_RECONNECT_ATTEMPTS: u32 = 4;
+                    let mut tool_calls: Vec<serde_json::Value> = vec![];
+                    let mut round_error: Option<OllamaRequestError> = None;
+
+                    'attempt: loop {
+                        let opts = web_sys::RequestInit::new();
+                        opts.set_method("POST");
+                        opts.set_body(&JsValue::from_str(&body.to_string()));
+
+                        let headers = web_sys::Headers::new().unwrap();
+                        headers.set("Content-Type", "application/json").unwrap();
+                        opts.set_headers(&headers);
+
+                        let request = web_sys::Request::new_with_str_and_init("/api/stream", &opts).unwrap();
+                        let resp_value = wasm_bindgen_futures::JsFuture::from(window.fetch_with_request(&request)).await;
+
+                        tool_calls.clear();
+                        let mut current_event = String::from("message");
+                        let mut saw_end = false;
+                        let mut stream_error: Option<String> = None;
+                        let mut attempt_error: Option<OllamaRequestError> = None;
+
+                        match resp_value {
+                            Err(_) => attempt_error = Some(OllamaRequestError::ConnectionRefused),
+                            Ok(resp) => {
+                                let resp: web_sys::Response = resp.dyn_into().unwrap();
+                                if !resp.ok() {
+                                    attempt_error = Some(OllamaRequestError::ServerError(resp.status()));
+                                } else if let Some(resp_body) = resp.body() {
+                                    let reader: web_sys::ReadableStreamDefaultReader = resp_body.get_reader().unchecked_into();
+
+                                    loop {
+                                        let read_promise = reader.read();
+                                        let result = wasm_bindgen_futures::JsFuture::from(read_promise).await;
+                                        if let Ok(chunk) = result {
+                                            let done = js_sys::Reflect::get(&chunk, &JsValue::from_str("done")).unwrap();
+
+                                            if done.as_bool().unwrap_or(true) {
+                                                break;
+                                            }
+
+                                            let value = js_sys::Reflect::get(&chunk, &JsValue::from_str("value")).unwrap();
+                                            let array: js_sys::Uint8Array = value.dyn_into().unwrap();
+                                            let bytes = array.to_vec();
+                                            let text = String::from_utf8_lossy(&bytes);
+
+                                            // Parse SSE format: "event:" lines select the event type
+                                            // that the following "data:" line(s) belong to.
+                                            for line in text.lines() {
+                                                if let Some(event) = line.strip_prefix("event:") {
+                                                    current_event = event.trim().to_string();
+                                                    continue;
+                                                }
+                                                if line.is_empty() {
+                                                    current_event = "message".to_string();
+                                                    continue;
+                                                }
+                                                let Some(data) = line.strip_prefix("data:") else { continue };
+                                                let data = data.trim();
+
+                                                match current_event.as_str() {
+                                                    "tool_calls" => {
+                                                        if let Ok(calls) = serde_json::from_str::<Vec<serde_json::Value>>(data) {
+                                                            tool_calls = calls;
+                                                        }
+                                                    }
+                                                    "error" => {
+                                                        stream_error = Some(data.to_string());
+                                                    }
+                                                    "stats" | "loading" | "generating" => {}
+                                                    _ => {
+                                                        if data == "__END__" {
+                                                            saw_end = true;
+                                                            continue;
+                                                        }
+                                                        if data.is_empty() {
+                                                            continue;
+                                                        }
+                                                        if let Some(msg) = data.strip_prefix("[Error: ").and_then(|s| s.strip_suffix(']')) {
+                                                            stream_error = Some(msg.to_string());
+                                                            continue;
+                                                        }
+                                                        full_text.push_str(data);
+                                                        full_text.push(' '); // Add space between chunks
+
+                                                        let current_text = full_text.clone();
+                                                        if let Some(last) = messages.get().last().copied() {
+                                                            last.update(|m| {
+                                                                if m.role == "ai" {
+                                                                    m.text = current_text.clone();
+                                                                }
+                                                            });
+                                                        }
+                                                        if let Some(message_id) = ai_message_id {
+                                                            persist_resumable_generation(&ResumableGeneration {
+                                                                message_id,
+                                                                model: model.clone(),
+                                                                history: resumable_history.clone(),
+                                                                partial_text: current_text,
+                                                            });
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        } else {
+                                            attempt_error = Some(OllamaRequestError::StreamInterrupted);
+                                            break;
+                                        }
+                                    }
+
+                                    if attempt_error.is_none() {
+                                        if let Some(msg) = stream_error {
+                                            attempt_error = Some(OllamaRequestError::from_stream_message(&msg));
+                                        } else if !saw_end {
+                                            attempt_error = Some(OllamaRequestError::StreamInterrupted);
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        match attempt_error {
+                            None => {
+                                set_reconnect_attempt.set(0);
+                                break 'attempt;
+                            }
+                            Some(err) if err.is_transient() && reconnect_attempt.get_untracked() < MAX_RECONNECT_ATTEMPTS => {
+                                let attempt_n = reconnect_attempt.get_untracked();
+                                set_reconnect_attempt.set(attempt_n + 1);
+                                let delay_ms = 500i32.saturating_mul(1 << attempt_n.min(6));
+                                wasm_sleep_ms(delay_ms.min(8000)).await;
+                            }
+                            Some(err) => {
+                                round_error = Some(err);
+                                break 'attempt;
                             }
-                            context.push_str(&format!(
-                                "---\nBased on the above web search results, please answer the following question:\n\n{}",
-                                user_query
-                            ));
-                            context
                         }
-                        _ => user_query.clone() // Fall back to original query if search fails
                     }
-                } else {
-                    user_query.clone()
-                };
+
+                    if let Some(err) = round_error {
+                        clear_resumable_generation();
+                        set_chat_request_error.set(Some(err));
+                        set_is_streaming.set(false);
+                        broadcast_sync(serde_json::json!({ "type": "streaming", "active": false }));
+                        return;
+                    }
+
+                    if tool_calls.is_empty() || round + 1 == MAX_TOOL_ROUNDS {
+                        break;
+                    }
+
+                    // The model wants to search the web - run each call and feed the
+                    // results back as a tool message, then let it answer grounded in them.
+                    chat_messages.push(serde_json::json!({ "role": "assistant", "content": "", "tool_calls": tool_calls }));
+                    for call in &tool_calls {
+                        let query = call["function"]["arguments"]["query"].as_str().unwrap_or_default().to_string();
+                        let result_text = match web_search(active_search_provider.clone(), query, active_search_credentials.clone()).await {
+                            Ok(response) if response.success => response.results.iter()
+                                .map(|r| format!("{}\n{}\n{}", r.title, r.url, r.description))
+                                .collect::<Vec<_>>()
+                                .join("\n\n"),
+                            Ok(response) => response.error.unwrap_or_else(|| "Search failed".to_string()),
+                            Err(e) => format!("Search error: {}", e),
+                        };
+                        chat_messages.push(serde_json::json!({ "role": "tool", "content": result_text }));
+                    }
+                }
+
+                clear_resumable_generation();
+                set_is_streaming.set(false);
+                broadcast_sync(serde_json::json!({ "type": "streaming", "active": false }));
+                if !is_foreground.get() {
+                    set_pending_notifications.update(|n| *n += 1);
+                    notify("Ollama Rust", "Response ready");
+                }
+            });
+        }
+    };
+
+    // Resends the last message after a failed chat request, without making
+    // the user retype it - restores it into the input box and re-runs the
+    // same send path used for a fresh message.
+    let retry_last_send = {
+        let do_send = do_send.clone();
+        move || {
+            if let Some((text, images)) = last_chat_request.get() {
+                set_chat_request_error.set(None);
+                set_input.set(text);
+                set_pending_images.set(images);
+                do_send();
+            }
+        }
+    };
+
+    // Picks a generation back up after `interrupted_generation` shows it was
+    // cut off by a reload. There's no real resume token, so this just
+    // re-issues the stored request and, as new tokens come in, reconciles
+    // them against the partial text already shown via `dedupe_overlap` -
+    // deliberately simpler than `do_send` (no tool-call rounds, no images)
+    // since this is a best-effort continuation, not a fresh send.
+    let resume_interrupted_generation = move || {
+        let Some(record) = interrupted_generation.get() else { return };
+        set_interrupted_generation.set(None);
+        #[cfg(target_arch = "wasm32")]
+        clear_resumable_generation();
+
+        if is_streaming.get() {
+            return;
+        }
+        set_is_streaming.set(true);
+        #[cfg(target_arch = "wasm32")]
+        broadcast_sync(serde_json::json!({ "type": "streaming", "active": true }));
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            use wasm_bindgen::prelude::*;
+            use wasm_bindgen::JsCast;
+
+            let ctx_window = num_ctx.get();
+            wasm_bindgen_futures::spawn_local(async move {
+                let window = web_sys::window().unwrap();
+                let chat_messages: Vec<serde_json::Value> = record.history.iter()
+                    .map(|(role, content)| serde_json::json!({ "role": role, "content": content }))
+                    .collect();
+                let body = serde_json::json!({
+                    "model": record.model,
+                    "messages": chat_messages,
+                    "options": { "num_ctx": ctx_window }
+                });
 
                 let opts = web_sys::RequestInit::new();
                 opts.set_method("POST");
-                opts.set_body(&JsValue::from_str(&serde_json::json!({
-                    "model": model,
-                    "prompt": prompt
-                }).to_string()));
-
+                opts.set_body(&JsValue::from_str(&body.to_string()));
                 let headers = web_sys::Headers::new().unwrap();
                 headers.set("Content-Type", "application/json").unwrap();
                 opts.set_headers(&headers);
-
                 let request = web_sys::Request::new_with_str_and_init("/api/stream", &opts).unwrap();
-
                 let resp_value = wasm_bindgen_futures::JsFuture::from(window.fetch_with_request(&request)).await;
 
+                let mut full_text = String::new();
                 if let Ok(resp) = resp_value {
                     let resp: web_sys::Response = resp.dyn_into().unwrap();
-                    if let Some(body) = resp.body() {
-                        let reader: web_sys::ReadableStreamDefaultReader = body.get_reader().unchecked_into();
-
-                        let mut full_text = String::new();
-
-                        loop {
-                            let read_promise = reader.read();
-                            let result = wasm_bindgen_futures::JsFuture::from(read_promise).await;
-                            if let Ok(chunk) = result {
+                    if resp.ok() {
+                        if let Some(resp_body) = resp.body() {
+                            let reader: web_sys::ReadableStreamDefaultReader = resp_body.get_reader().unchecked_into();
+                            let mut current_event = String::from("message");
+
+                            loop {
+                                let read_promise = reader.read();
+                                let Ok(chunk) = wasm_bindgen_futures::JsFuture::from(read_promise).await else { break };
                                 let done = js_sys::Reflect::get(&chunk, &JsValue::from_str("done")).unwrap();
-
                                 if done.as_bool().unwrap_or(true) {
                                     break;
                                 }
-
                                 let value = js_sys::Reflect::get(&chunk, &JsValue::from_str("value")).unwrap();
                                 let array: js_sys::Uint8Array = value.dyn_into().unwrap();
-                                let bytes = array.to_vec();
-                                let text = String::from_utf8_lossy(&bytes);
+                                let text = String::from_utf8_lossy(&array.to_vec());
 
-                                // Parse SSE format
                                 for line in text.lines() {
-                                    if line.starts_with("data:") {
-                                        let data = line.trim_start_matches("data:").trim();
-                                        if data == "__END__" || data.is_empty() {
-                                            if data == "__END__" {
-                                                set_is_streaming.set(false);
-                                            }
-                                            break;
-                                        }
-                                        full_text.push_str(data);
-                                        full_text.push(' '); // Add space between chunks
-
-                                        let current_text = full_text.clone();
-                                        set_messages.update(|msgs| {
-                                            if let Some(last) = msgs.last_mut() {
-                                                if last.role == "ai" {
-                                                    last.text = current_text;
-                                                }
-                                            }
-                                        });
+                                    if let Some(event) = line.strip_prefix("event:") {
+                                        current_event = event.trim().to_string();
+                                        continue;
+                                    }
+                                    if line.is_empty() {
+                                        current_event = "message".to_string();
+                                        continue;
+                                    }
+                                    let Some(data) = line.strip_prefix("data:") else { continue };
+                                    let data = data.trim();
+                                    if current_event != "message" || data.is_empty() || data == "__END__" || data.starts_with("[Error:") {
+                                        continue;
+                                    }
+
+                                    full_text.push_str(data);
+                                    full_text.push(' ');
+                                    let shown = format!("{}{}", record.partial_text, dedupe_overlap(&record.partial_text, full_text.trim_end()));
+                                    if let Some(msg) = messages.get().iter().find(|m| m.get_untracked().id == record.message_id).copied() {
+                                        msg.update(|m| m.text = shown);
                                     }
                                 }
-                            } else {
-                                break;
                             }
                         }
                     }
                 }
+
                 set_is_streaming.set(false);
+                broadcast_sync(serde_json::json!({ "type": "streaming", "active": false }));
+            });
+        }
+    };
+
+    // Tally an emoji reaction on a stored message, picked from the reaction
+    // picker; persisted alongside the rest of the chat history by the effect above.
+    let add_reaction = move |index: usize, emoji: String| {
+        if let Some(msg) = messages.get().get(index).copied() {
+            msg.update(|m| {
+                *m.reactions.entry(emoji).or_insert(0) += 1;
+            });
+        }
+    };
+
+    // Clicking a rendered reaction pill removes it outright.
+    let remove_reaction = move |index: usize, emoji: String| {
+        if let Some(msg) = messages.get().get(index).copied() {
+            msg.update(|m| {
+                m.reactions.remove(&emoji);
             });
         }
     };
 
-    // Close all menus
-    let close_menus = move || {
-        set_menu_open.set(false);
-        set_models_panel_open.set(false);
-        set_cloud_panel_open.set(false);
+    // Opens the full-screen media viewer on a fresh thumbnail, resetting any
+    // zoom/pan left over from the last image it showed.
+    let open_media_viewer = move |src: String| {
+        set_media_viewer_src.set(Some(src));
+        set_media_viewer_scale.set(1.0);
+        set_media_viewer_offset.set((0.0, 0.0));
+        set_media_viewer_pointers.set(HashMap::new());
+        set_media_viewer_drag_anchor.set(None);
+        set_media_viewer_pinch_anchor.set(None);
+        set_media_viewer_moved.set(false);
+    };
+
+    let media_viewer_pointer_down = move |ev: web_sys::PointerEvent| {
+        ev.stop_propagation();
+        let id = ev.pointer_id();
+        let pos = (ev.client_x() as f64, ev.client_y() as f64);
+        set_media_viewer_moved.set(false);
+        set_media_viewer_pointers.update(|pointers| { pointers.insert(id, pos); });
+        let pointers = media_viewer_pointers.get();
+        if pointers.len() == 1 {
+            set_media_viewer_drag_anchor.set(Some((pos, media_viewer_offset.get())));
+        } else if pointers.len() == 2 {
+            let mut vals = pointers.values();
+            let a = *vals.next().unwrap();
+            let b = *vals.next().unwrap();
+            let dist = ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt();
+            set_media_viewer_pinch_anchor.set(Some((dist, media_viewer_scale.get())));
+        }
+    };
+
+    let media_viewer_pointer_move = move |ev: web_sys::PointerEvent| {
+        let id = ev.pointer_id();
+        if !media_viewer_pointers.get().contains_key(&id) {
+            return;
+        }
+        let pos = (ev.client_x() as f64, ev.client_y() as f64);
+        set_media_viewer_moved.set(true);
+        set_media_viewer_pointers.update(|pointers| { pointers.insert(id, pos); });
+        let pointers = media_viewer_pointers.get();
+        if pointers.len() >= 2 {
+            if let Some((start_dist, start_scale)) = media_viewer_pinch_anchor.get() {
+                let mut vals = pointers.values();
+                let a = *vals.next().unwrap();
+                let b = *vals.next().unwrap();
+                let dist = ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt();
+                if start_dist > 0.0 {
+                    set_media_viewer_scale.set((start_scale * dist / start_dist).clamp(1.0, 4.0));
+                }
+            }
+        } else if pointers.len() == 1 {
+            if let Some((anchor_pos, anchor_offset)) = media_viewer_drag_anchor.get() {
+                let scale = media_viewer_scale.get();
+                let max_offset = (scale - 1.0).max(0.0) * 200.0 + 40.0;
+                let new_x = (anchor_offset.0 + (pos.0 - anchor_pos.0) / scale).clamp(-max_offset, max_offset);
+                let new_y = (anchor_offset.1 + (pos.1 - anchor_pos.1) / scale).clamp(-max_offset, max_offset);
+                set_media_viewer_offset.set((new_x, new_y));
+            }
+        }
+    };
+
+    let media_viewer_pointer_end = move |ev: web_sys::PointerEvent| {
+        let id = ev.pointer_id();
+        set_media_viewer_pointers.update(|pointers| { pointers.remove(&id); });
+        let remaining = media_viewer_pointers.get();
+        if remaining.len() < 2 {
+            set_media_viewer_pinch_anchor.set(None);
+        }
+        if remaining.len() == 1 {
+            let pos = *remaining.values().next().unwrap();
+            set_media_viewer_drag_anchor.set(Some((pos, media_viewer_offset.get())));
+        } else if remaining.is_empty() {
+            set_media_viewer_drag_anchor.set(None);
+        }
+    };
+
+    // Regenerate an assistant turn: drop it and its prompt, then resend the
+    // same prompt text through `do_send`'s normal streaming path (including
+    // its web-search tool-call rounds) instead of retyping it.
+    let regenerate = move |index: usize| {
+        if is_streaming.get() {
+            return;
+        }
+        let msgs = messages.get();
+        if index == 0 || index >= msgs.len() || msgs[index].get().role != "ai" {
+            return;
+        }
+        let prompt_text = msgs[index - 1].get().text.clone();
+        set_messages.update(|msgs| msgs.truncate(index - 1));
+        set_input.set(prompt_text);
+        do_send();
+    };
+
+    // Reads each picked file as a base64 data URL and stages it in
+    // `pending_images`; the server downscales oversized captures before they
+    // ever reach the model.
+    #[cfg(target_arch = "wasm32")]
+    let handle_image_files = move |files: web_sys::FileList| {
+        use wasm_bindgen::prelude::*;
+        use wasm_bindgen::JsCast;
+
+        for i in 0..files.length() {
+            let Some(file) = files.get(i) else { continue };
+            let Ok(reader) = web_sys::FileReader::new() else { continue };
+            let reader_clone = reader.clone();
+            let cb = Closure::wrap(Box::new(move |_: web_sys::ProgressEvent| {
+                if let Ok(result) = reader_clone.result() {
+                    if let Some(data_url) = result.as_string() {
+                        set_pending_images.update(|imgs| imgs.push(data_url));
+                    }
+                }
+            }) as Box<dyn FnMut(_)>);
+            reader.set_onload(Some(cb.as_ref().unchecked_ref()));
+            cb.forget();
+            let _ = reader.read_as_data_url(&file);
+        }
+    };
+
+    // Close all menus. Mirrors the `navigate("/models", ...)` etc. the open
+    // paths (`open_models_panel`, the status toggle) do, so a panel closed
+    // via backdrop tap doesn't leave the URL pointing at a route that's no
+    // longer actually open - a reload would otherwise reopen it.
+    let close_menus = {
+        let navigate = navigate.clone();
+        move || {
+            set_menu_open.set(false);
+            set_models_panel_open.set(false);
+            set_cloud_panel_open.set(false);
+            navigate("/", Default::default());
+        }
+    };
+
+    // One-tap "Pull" from the catalog browser - pre-fills the relevant
+    // add-model input and drives the same flow the manual input row does.
+    let pull_from_catalog = move |name: String| {
+        match show_catalog.get() {
+            Some(CatalogTarget::Cloud) => {
+                set_selected_model.set(Some(format!("cloud:{}", name)));
+                set_new_cloud_model_name.set(String::new());
+                set_show_add_cloud_model.set(false);
+                close_menus();
+            }
+            _ => {
+                set_new_model_name.set(name.clone());
+                start_download(name);
+            }
+        }
+        set_show_catalog.set(None);
+        set_catalog_search.set(String::new());
     };
 
     // Toggle menu
@@ -1253,7 +4343,7 @@ pub fn App() -> impl IntoView {
         }
     };
 
-    // Select model and persist to localStorage
+    // Select model, persist to localStorage, and sync the choice to other tabs
     let select_model = move |model: String| {
         set_selected_model.set(Some(model.clone()));
         #[cfg(target_arch = "wasm32")]
@@ -1263,19 +4353,204 @@ pub fn App() -> impl IntoView {
                     let _ = storage.set_item("selected_model", &model);
                 }
             }
+            broadcast_sync(serde_json::json!({ "type": "select_model", "model": model }));
         }
         close_menus();
     };
 
     // Handle runner item interaction (hover/click)
-    let open_models_panel = move |ev: web_sys::MouseEvent| {
-        ev.stop_propagation();
-        set_models_panel_open.set(true);
+    let open_models_panel = {
+        let navigate = navigate.clone();
+        move |ev: web_sys::MouseEvent| {
+            ev.stop_propagation();
+            set_models_panel_open.set(true);
+            navigate("/models", Default::default());
+        }
+    };
+
+    // Fetch the model list from the configured OpenAI-compatible server
+    let fetch_openai_models = move || {
+        let base_url = openai_base_url.get();
+        let api_key = openai_api_key.get();
+        if base_url.trim().is_empty() {
+            return;
+        }
+        spawn_local(async move {
+            match get_openai_models(base_url, api_key).await {
+                Ok(response) if response.success => {
+                    set_openai_models.set(response.models);
+                    set_openai_models_error.set(None);
+                }
+                Ok(response) => {
+                    set_openai_models.set(vec![]);
+                    set_openai_models_error.set(response.error);
+                }
+                Err(e) => {
+                    set_openai_models.set(vec![]);
+                    set_openai_models_error.set(Some(format!("Request failed: {}", e)));
+                }
+            }
+        });
+    };
+
+    // Fetch the model list for one registered remote server and merge it
+    // into the shared `remote_models` map keyed by server id.
+    let fetch_remote_models = move |server_id: String, base_url: String| {
+        spawn_local(async move {
+            if let Ok(status) = list_remote_models(base_url).await {
+                set_remote_models.update(|map| {
+                    map.insert(server_id, status.models);
+                });
+            }
+        });
+    };
+
+    // Register a new remote Ollama server and immediately fetch its models
+    let add_remote_server_action = move || {
+        let name = new_remote_name.get();
+        let url = new_remote_url.get();
+        if url.trim().is_empty() {
+            return;
+        }
+        let fetch = fetch_remote_models;
+        spawn_local(async move {
+            if let Ok(server) = add_remote_server(name, url).await {
+                let id = server.id.clone();
+                let base_url = server.base_url.clone();
+                set_remote_servers.update(|servers| servers.push(server));
+                fetch(id, base_url);
+            }
+        });
+        set_new_remote_name.set(String::new());
+        set_new_remote_url.set(String::new());
+        set_show_add_remote.set(false);
+    };
+
+    // Unregister a remote server and drop its cached model list
+    let remove_remote_server_action = move |id: String| {
+        let id_for_task = id.clone();
+        spawn_local(async move {
+            let _ = remove_remote_server(id_for_task).await;
+        });
+        set_remote_servers.update(|servers| servers.retain(|s| s.id != id));
+        set_remote_models.update(|map| { map.remove(&id); });
+        if selected_model.get().as_ref().is_some_and(|m| m.starts_with(&format!("remote:{}:", id))) {
+            set_selected_model.set(None);
+        }
+    };
+
+    // Start a pull on a remote server, keyed in `active_downloads` as
+    // `remote:{server_id}:{model}` so it shares the progress bar UI and the
+    // long-poll/EMA speed tracking that the local runner uses.
+    let start_remote_download = move |server: RemoteServer, model_name: String| {
+        if model_name.trim().is_empty() {
+            return;
+        }
+        let progress_key = format!("remote:{}:{}", server.id, model_name.trim());
+        let downloads = active_downloads.get();
+        if downloads.iter().any(|d| d.model == progress_key && !d.done) {
+            return;
+        }
+
+        set_active_downloads.update(|downloads| {
+            downloads.push(PullProgress {
+                model: progress_key,
+                status: "Starting...".to_string(),
+                percent: 0.0,
+                done: false,
+                error: None,
+                bytes_downloaded: 0,
+                total_bytes: 0,
+                speed: "".to_string(),
+                eta: "".to_string(),
+                speed_bps: 0.0,
+                last_update: 0,
+                last_sample_ms: 0,
+            });
+        });
+
+        let server_id = server.id.clone();
+        let base_url = server.base_url.clone();
+        let model = model_name.trim().to_string();
+        spawn_local(async move {
+            let _ = start_remote_model_pull(server_id, base_url, model).await;
+        });
+
+        set_new_remote_model_name.set(String::new());
+        set_show_add_remote_model.set(None);
+    };
+
+    // Delete a model from a remote server and refresh that server's list
+    let do_delete_remote_model = move |server: RemoteServer, model_name: String| {
+        let key = format!("{}:{}", server.id, model_name);
+        set_deleting_remote_model.set(Some(key.clone()));
+
+        let server_id = server.id.clone();
+        let base_url = server.base_url.clone();
+        let model = model_name.clone();
+        let fetch = fetch_remote_models;
+        spawn_local(async move {
+            if let Ok(true) = delete_remote_model(base_url.clone(), model).await {
+                fetch(server_id, base_url);
+            }
+            set_deleting_remote_model.set(None);
+        });
     };
 
+    // Load the registered remote servers on mount and fetch each one's
+    // models the first time it shows up.
+    Effect::new(move |_| {
+        if let Some(Ok(servers)) = remote_servers_resource.get() {
+            set_remote_servers.set(servers.clone());
+            for server in servers {
+                if !remote_models.get().contains_key(&server.id) {
+                    fetch_remote_models(server.id, server.base_url);
+                }
+            }
+        }
+    });
+
     view! {
         <Stylesheet id="leptos" href="/pkg/ollama-rust.css"/>
-        <Title text="Ollama Rust"/>
+        <Router>
+        // Tab title and Android task-switcher label follow the active model
+        // and stream state, so a long generation stays visible at a glance.
+        <Title
+            formatter=|text| format!("{text} · Ollama Rust")
+            text=move || {
+                let model = selected_model.get().unwrap_or_else(|| "No model".to_string());
+                let label = if is_streaming.get() {
+                    format!("{model} — generating…")
+                } else {
+                    model
+                };
+                let count = pending_notifications.get();
+                if count > 0 {
+                    format!("({count}) {label}")
+                } else {
+                    label
+                }
+            }
+        />
+        <Meta name="theme-color" content=move || {
+            let theme = current_theme.get();
+            if theme == "custom" {
+                custom_theme_colors.get().background
+            } else {
+                preset_theme_colors(&theme).background
+            }
+        }/>
+
+        // The chat/models/settings "pages" are really overlays on one
+        // single-page layout below, not separately mounted route components -
+        // these routes exist so the URL, Android back button, and restored
+        // deep links open the right overlay instead of switching content.
+        <Routes fallback=|| "">
+            <Route path=path!("/") view=|| () />
+            <Route path=path!("/chat/:id") view=ChatRoute />
+            <Route path=path!("/models") view=ModelsRoute />
+            <Route path=path!("/settings") view=SettingsRoute />
+        </Routes>
 
         // Backdrop to close menus when clicking outside
         <div class="menu-backdrop"
@@ -1311,7 +4586,7 @@ pub fn App() -> impl IntoView {
                              on:click=move |ev: web_sys::MouseEvent| ev.stop_propagation()>
                             <div class="runner-list">
                                 <div class="runner-item"
-                                     on:mouseenter=open_models_panel
+                                     on:mouseenter=open_models_panel.clone()
                                      on:click=open_models_panel
                                      on:touchstart=move |ev: web_sys::TouchEvent| {
                                          ev.stop_propagation();
@@ -1325,14 +4600,14 @@ pub fn App() -> impl IntoView {
                                          on:click=move |ev: web_sys::MouseEvent| ev.stop_propagation()>
                                         // Add Model section
                                         <div class="add-model-section">
-                                            // Library link
-                                            <a href="https://ollama.com/library"
-                                               target="_blank"
-                                               rel="noopener noreferrer"
-                                               class="model-option library-link"
-                                               on:click=move |ev: web_sys::MouseEvent| ev.stop_propagation()>
+                                            // Catalog browser
+                                            <div class="model-option library-link"
+                                                 on:click=move |ev: web_sys::MouseEvent| {
+                                                     ev.stop_propagation();
+                                                     set_show_catalog.set(Some(CatalogTarget::Local));
+                                                 }>
                                                 "📚 Browse Models"
-                                            </a>
+                                            </div>
 
                                             {move || if show_add_model.get() {
                                                 view! {
@@ -1390,20 +4665,65 @@ pub fn App() -> impl IntoView {
                                         // Divider
                                         <div class="model-divider"></div>
 
+                                        // Filter input - supports name:/family:/size:/quant: tokens
+                                        // plus fuzzy free text, see `parse_model_filter_query`.
+                                        <input
+                                            type="text"
+                                            class="model-filter-input"
+                                            placeholder="Filter models (name:, family:, size:, quant:)"
+                                            prop:value=move || local_model_filter.get()
+                                            on:input=move |ev| set_local_model_filter.set(event_target_value(&ev))
+                                            on:click=move |ev: web_sys::MouseEvent| ev.stop_propagation()
+                                            on:keydown=move |ev: web_sys::KeyboardEvent| {
+                                                ev.stop_propagation();
+                                                if ev.key() == "Enter" {
+                                                    let typed = local_model_filter.get();
+                                                    if typed.trim().is_empty() {
+                                                        return;
+                                                    }
+                                                    let query = parse_model_filter_query(&typed);
+                                                    let has_match = status_resource.get()
+                                                        .and_then(|r| r.ok())
+                                                        .map(|s| s.models.iter().any(|m| model_matches_filter(m, m, "", &query)))
+                                                        .unwrap_or(false);
+                                                    if !has_match {
+                                                        select_model(typed.trim().to_string());
+                                                        set_local_model_filter.set(String::new());
+                                                    }
+                                                }
+                                            }
+                                        />
+
                                         // Models list
                                         <Suspense fallback=move || view! { <div class="loading-models">"Loading..."</div> }>
                                             {move || {
                                                 status_resource.get().map(|result| {
                                                     match result {
                                                         Ok(status) => {
-                                                            if status.models.is_empty() {
+                                                            let filter_query = parse_model_filter_query(&local_model_filter.get());
+                                                            let filtered_models: Vec<String> = status.models.into_iter()
+                                                                .filter(|m| model_matches_filter(m, m, "", &filter_query))
+                                                                .collect();
+                                                            if filtered_models.is_empty() && local_model_filter.get().trim().is_empty() {
                                                                 view! {
                                                                     <div class="no-models">"Turn on Ollama to view installed models"</div>
                                                                 }.into_any()
+                                                            } else if filtered_models.is_empty() {
+                                                                let typed = local_model_filter.get();
+                                                                view! {
+                                                                    <div class="model-option model-filter-commit"
+                                                                         on:click=move |ev: web_sys::MouseEvent| {
+                                                                             ev.stop_propagation();
+                                                                             select_model(typed.trim().to_string());
+                                                                             set_local_model_filter.set(String::new());
+                                                                         }>
+                                                                        "No matches - press Enter or tap to use \"" {typed} "\""
+                                                                    </div>
+                                                                }.into_any()
                                                             } else {
                                                                 view! {
                                                                     <div id="ollama-models" class="model-submenu">
-                                                                        {status.models.into_iter().map(|model| {
+                                                                        {filtered_models.into_iter().map(|model| {
                                                                             let m_click = model.clone();
                                                                             let m_touch = model.clone();
                                                                             let m_display = model.clone();
@@ -1439,7 +4759,7 @@ pub fn App() -> impl IntoView {
                                                                                         disabled=is_deleting()
                                                                                         on:click=move |ev: web_sys::MouseEvent| {
                                                                                             ev.stop_propagation();
-                                                                                            do_delete_model(m_delete.clone());
+                                                                                            set_confirm_delete.set(Some(PendingDelete::Local(m_delete.clone())));
                                                                                         }>
                                                                                         {if is_deleting() { "..." } else { "❌" }}
                                                                                     </button>
@@ -1517,13 +4837,13 @@ pub fn App() -> impl IntoView {
 
                                                 // Add Cloud Model section
                                                 <div class="add-model-section">
-                                                    <a href="https://ollama.com/library"
-                                                       target="_blank"
-                                                       rel="noopener noreferrer"
-                                                       class="model-option library-link"
-                                                       on:click=move |ev: web_sys::MouseEvent| ev.stop_propagation()>
+                                                    <div class="model-option library-link"
+                                                         on:click=move |ev: web_sys::MouseEvent| {
+                                                             ev.stop_propagation();
+                                                             set_show_catalog.set(Some(CatalogTarget::Cloud));
+                                                         }>
                                                         "📚 Browse Models"
-                                                    </a>
+                                                    </div>
 
                                                     {move || if show_add_cloud_model.get() {
                                                         view! {
@@ -1590,19 +4910,66 @@ pub fn App() -> impl IntoView {
 
                                                 <div class="model-divider"></div>
 
+                                                // Filter input - supports name:/family:/size:/quant: tokens
+                                                // plus fuzzy free text, see `parse_model_filter_query`.
+                                                <input
+                                                    type="text"
+                                                    class="model-filter-input"
+                                                    placeholder="Filter models (name:, family:, size:, quant:)"
+                                                    prop:value=move || cloud_model_filter.get()
+                                                    on:input=move |ev| set_cloud_model_filter.set(event_target_value(&ev))
+                                                    on:click=move |ev: web_sys::MouseEvent| ev.stop_propagation()
+                                                    on:keydown=move |ev: web_sys::KeyboardEvent| {
+                                                        ev.stop_propagation();
+                                                        if ev.key() == "Enter" {
+                                                            let typed = cloud_model_filter.get();
+                                                            if typed.trim().is_empty() {
+                                                                return;
+                                                            }
+                                                            let query = parse_model_filter_query(&typed);
+                                                            let has_match = cloud_models_resource.get()
+                                                                .and_then(|r| r.ok())
+                                                                .map(|r| r.models.iter().any(|m| model_matches_filter(&m.name, &m.display_name, &m.description, &query)))
+                                                                .unwrap_or(false);
+                                                            if !has_match {
+                                                                set_selected_model.set(Some(format!("cloud:{}", typed.trim())));
+                                                                set_cloud_model_filter.set(String::new());
+                                                                close_menus();
+                                                            }
+                                                        }
+                                                    }
+                                                />
+
                                                 <Suspense fallback=move || view! { <div class="loading-models">"Loading cloud models..."</div> }>
                                                     {move || {
                                                         cloud_models_resource.get().map(|result| {
                                                             match result {
                                                                 Ok(response) => {
-                                                                    if response.models.is_empty() {
+                                                                    let filter_query = parse_model_filter_query(&cloud_model_filter.get());
+                                                                    let filtered_models: Vec<CloudModel> = response.models.into_iter()
+                                                                        .filter(|m| model_matches_filter(&m.name, &m.display_name, &m.description, &filter_query))
+                                                                        .collect();
+                                                                    if filtered_models.is_empty() && cloud_model_filter.get().trim().is_empty() {
                                                                         view! {
                                                                             <div class="no-models">"No cloud models available"</div>
                                                                         }.into_any()
+                                                                    } else if filtered_models.is_empty() {
+                                                                        let typed = cloud_model_filter.get();
+                                                                        view! {
+                                                                            <div class="cloud-model-option model-filter-commit"
+                                                                                 on:click=move |ev: web_sys::MouseEvent| {
+                                                                                     ev.stop_propagation();
+                                                                                     set_selected_model.set(Some(format!("cloud:{}", typed.trim())));
+                                                                                     set_cloud_model_filter.set(String::new());
+                                                                                     close_menus();
+                                                                                 }>
+                                                                                "No matches - press Enter or tap to use \"" {typed} "\""
+                                                                            </div>
+                                                                        }.into_any()
                                                                     } else {
                                                                         view! {
                                                                             <div class="cloud-models-list">
-                                                                                {response.models.into_iter().map(|model| {
+                                                                                {filtered_models.into_iter().map(|model| {
                                                                                     let m_click = model.name.clone();
                                                                                     let m_display = model.display_name.clone();
                                                                                     let m_desc = model.description.clone();
@@ -1640,7 +5007,132 @@ pub fn App() -> impl IntoView {
                                                         }
                                                     })}
 
-                                                    {move || if show_email_login.get() {
+                                                    {move || if let Some((existing_provider, attempted_provider)) = account_link_prompt.get() {
+                                                        // The email is already tied to a different provider - offer to
+                                                        // link rather than dead-end on a generic error. When the existing
+                                                        // identity is itself email/password, there's no OAuth device flow
+                                                        // to re-run for it - collect the password instead.
+                                                        if existing_provider == "email" {
+                                                            let link_with = attempted_provider.clone();
+                                                            view! {
+                                                                <div class="account-link-section">
+                                                                    <div class="account-link-message">
+                                                                        "An account with this email already uses a password to sign in. Enter it to link "
+                                                                        <strong>{attempted_provider.clone()}</strong>
+                                                                        "."
+                                                                    </div>
+                                                                    <input
+                                                                        type="email"
+                                                                        class="cloud-login-input"
+                                                                        placeholder="Email"
+                                                                        prop:value=move || cloud_email.get()
+                                                                        on:input=move |ev| set_cloud_email.set(event_target_value(&ev))
+                                                                        on:click=move |ev: web_sys::MouseEvent| ev.stop_propagation()
+                                                                    />
+                                                                    <input
+                                                                        type="password"
+                                                                        class="cloud-login-input"
+                                                                        placeholder="Password"
+                                                                        prop:value=move || cloud_password.get()
+                                                                        on:input=move |ev| set_cloud_password.set(event_target_value(&ev))
+                                                                        on:click=move |ev: web_sys::MouseEvent| ev.stop_propagation()
+                                                                        on:keydown=move |ev: web_sys::KeyboardEvent| {
+                                                                            ev.stop_propagation();
+                                                                            if ev.key() == "Enter" {
+                                                                                do_link_email_account(link_with.clone());
+                                                                            }
+                                                                        }
+                                                                    />
+                                                                    <button
+                                                                        class="cloud-login-btn"
+                                                                        disabled=move || cloud_login_pending.get()
+                                                                        on:click=move |ev: web_sys::MouseEvent| {
+                                                                            ev.stop_propagation();
+                                                                            do_link_email_account(link_with.clone());
+                                                                        }>
+                                                                        "Link this account"
+                                                                    </button>
+                                                                    <button
+                                                                        class="cloud-back-btn"
+                                                                        on:click=move |ev: web_sys::MouseEvent| {
+                                                                            ev.stop_propagation();
+                                                                            set_account_link_prompt.set(None);
+                                                                            set_cloud_login_error.set(None);
+                                                                        }>
+                                                                        "← Back to other options"
+                                                                    </button>
+                                                                </div>
+                                                            }.into_any()
+                                                        } else {
+                                                            let link_target = existing_provider.clone();
+                                                            view! {
+                                                                <div class="account-link-section">
+                                                                    <div class="account-link-message">
+                                                                        "An account with this email already uses "
+                                                                        <strong>{existing_provider.clone()}</strong>
+                                                                        " to sign in."
+                                                                    </div>
+                                                                    <button
+                                                                        class="cloud-login-btn"
+                                                                        disabled=move || cloud_login_pending.get()
+                                                                        on:click=move |ev: web_sys::MouseEvent| {
+                                                                            ev.stop_propagation();
+                                                                            do_oauth_login(link_target.clone(), Some(attempted_provider.clone()));
+                                                                        }>
+                                                                        "Link this account"
+                                                                    </button>
+                                                                    <button
+                                                                        class="cloud-back-btn"
+                                                                        on:click=move |ev: web_sys::MouseEvent| {
+                                                                            ev.stop_propagation();
+                                                                            set_account_link_prompt.set(None);
+                                                                            set_cloud_login_error.set(None);
+                                                                        }>
+                                                                        "← Back to other options"
+                                                                    </button>
+                                                                </div>
+                                                            }.into_any()
+                                                        }
+                                                    } else if let Some(authorization) = device_authorization.get() {
+                                                        // Device-code flow in progress - show the code to enter
+                                                        // at the provider's verification page while we poll.
+                                                        let verification_uri = authorization.verification_uri.clone();
+                                                        let verification_uri_open = verification_uri.clone();
+                                                        let user_code = authorization.user_code.clone();
+                                                        let user_code_copy = user_code.clone();
+                                                        view! {
+                                                            <div class="device-auth-section">
+                                                                <div class="device-auth-label">"Enter this code at"</div>
+                                                                <a class="device-auth-uri" href=verification_uri_open target="_blank" rel="noopener noreferrer">
+                                                                    {verification_uri}
+                                                                </a>
+                                                                <div class="device-auth-code"
+                                                                     on:click=move |ev: web_sys::MouseEvent| {
+                                                                         ev.stop_propagation();
+                                                                         if let Some(window) = web_sys::window() {
+                                                                             let _ = window.navigator().clipboard().write_text(&user_code_copy);
+                                                                         }
+                                                                         set_device_auth_copied.set(true);
+                                                                     }>
+                                                                    {user_code}
+                                                                </div>
+                                                                <div class="device-auth-hint">
+                                                                    {move || if device_auth_copied.get() { "Copied! " } else { "" }}
+                                                                    "Tap the code to copy it, waiting for confirmation..."
+                                                                </div>
+                                                                <button
+                                                                    class="cloud-back-btn"
+                                                                    on:click=move |ev: web_sys::MouseEvent| {
+                                                                        ev.stop_propagation();
+                                                                        set_device_authorization.set(None);
+                                                                        set_device_auth_provider.set(None);
+                                                                        set_cloud_login_error.set(None);
+                                                                    }>
+                                                                    "← Cancel sign-in"
+                                                                </button>
+                                                            </div>
+                                                        }.into_any()
+                                                    } else if show_email_login.get() {
                                                         // Email/password form
                                                         view! {
                                                             <input
@@ -1705,7 +5197,7 @@ pub fn App() -> impl IntoView {
                                                                 disabled=move || cloud_login_pending.get()
                                                                 on:click=move |ev: web_sys::MouseEvent| {
                                                                     ev.stop_propagation();
-                                                                    do_oauth_login("google".to_string());
+                                                                    do_oauth_login("google".to_string(), None);
                                                                 }>
                                                                 <svg class="oauth-icon" viewBox="0 0 24 24">
                                                                     <path fill="currentColor" d="M22.56 12.25c0-.78-.07-1.53-.2-2.25H12v4.26h5.92c-.26 1.37-1.04 2.53-2.21 3.31v2.77h3.57c2.08-1.92 3.28-4.74 3.28-8.09z"/>
@@ -1721,7 +5213,7 @@ pub fn App() -> impl IntoView {
                                                                 disabled=move || cloud_login_pending.get()
                                                                 on:click=move |ev: web_sys::MouseEvent| {
                                                                     ev.stop_propagation();
-                                                                    do_oauth_login("github".to_string());
+                                                                    do_oauth_login("github".to_string(), None);
                                                                 }>
                                                                 <svg class="oauth-icon" viewBox="0 0 24 24">
                                                                     <path fill="currentColor" d="M12 0c-6.626 0-12 5.373-12 12 0 5.302 3.438 9.8 8.207 11.387.599.111.793-.261.793-.577v-2.234c-3.338.726-4.033-1.416-4.033-1.416-.546-1.387-1.333-1.756-1.333-1.756-1.089-.745.083-.729.083-.729 1.205.084 1.839 1.237 1.839 1.237 1.07 1.834 2.807 1.304 3.492.997.107-.775.418-1.305.762-1.604-2.665-.305-5.467-1.334-5.467-5.931 0-1.311.469-2.381 1.236-3.221-.124-.303-.535-1.524.117-3.176 0 0 1.008-.322 3.301 1.23.957-.266 1.983-.399 3.003-.404 1.02.005 2.047.138 3.006.404 2.291-1.552 3.297-1.23 3.297-1.23.653 1.653.242 2.874.118 3.176.77.84 1.235 1.911 1.235 3.221 0 4.609-2.807 5.624-5.479 5.921.43.372.823 1.102.823 2.222v3.293c0 .319.192.694.801.576 4.765-1.589 8.199-6.086 8.199-11.386 0-6.627-5.373-12-12-12z"/>
@@ -1748,8 +5240,314 @@ pub fn App() -> impl IntoView {
                                                         }.into_any()
                                                     }}
                                                 </div>
-                                            }.into_any()
-                                        }}
+                                            }.into_any()
+                                        }}
+                                    </div>
+                                </div>
+
+                                // OpenAI-compatible runner item - base URL + API key, own model list
+                                <div class="runner-item openai-runner"
+                                     on:mouseenter=move |ev: web_sys::MouseEvent| {
+                                         ev.stop_propagation();
+                                         set_openai_panel_open.set(true);
+                                     }
+                                     on:mouseleave=move |ev: web_sys::MouseEvent| {
+                                         ev.stop_propagation();
+                                         set_openai_panel_open.set(false);
+                                     }
+                                     on:click=move |ev: web_sys::MouseEvent| {
+                                         ev.stop_propagation();
+                                         set_openai_panel_open.set(true);
+                                     }
+                                     on:touchstart=move |ev: web_sys::TouchEvent| {
+                                         ev.stop_propagation();
+                                         set_openai_panel_open.set(true);
+                                     }>
+                                    <div class="runner-name">"OpenAI-compatible"</div>
+
+                                    <div id="openai-panel"
+                                         class="models-panel openai-panel"
+                                         class:hidden=move || !openai_panel_open.get()
+                                         on:click=move |ev: web_sys::MouseEvent| ev.stop_propagation()>
+                                        <div class="openai-config-section">
+                                            <input
+                                                type="text"
+                                                class="openai-config-input"
+                                                placeholder="Base URL (e.g. https://api.openai.com/v1)"
+                                                prop:value=move || openai_base_url.get()
+                                                on:input=move |ev| set_openai_base_url.set(event_target_value(&ev))
+                                                on:click=move |ev: web_sys::MouseEvent| ev.stop_propagation()
+                                                on:keydown=move |ev: web_sys::KeyboardEvent| ev.stop_propagation()
+                                            />
+                                            <input
+                                                type="password"
+                                                class="openai-config-input"
+                                                placeholder="API Key"
+                                                prop:value=move || openai_api_key.get()
+                                                on:input=move |ev| set_openai_api_key.set(event_target_value(&ev))
+                                                on:click=move |ev: web_sys::MouseEvent| ev.stop_propagation()
+                                                on:keydown=move |ev: web_sys::KeyboardEvent| ev.stop_propagation()
+                                            />
+                                            <button
+                                                class="openai-save-btn"
+                                                on:click=move |ev: web_sys::MouseEvent| {
+                                                    ev.stop_propagation();
+                                                    #[cfg(target_arch = "wasm32")]
+                                                    {
+                                                        if let Some(window) = web_sys::window() {
+                                                            if let Ok(Some(storage)) = window.local_storage() {
+                                                                let _ = storage.set_item("openai_base_url", &openai_base_url.get());
+                                                                let _ = storage.set_item("openai_api_key", &openai_api_key.get());
+                                                            }
+                                                        }
+                                                    }
+                                                    fetch_openai_models();
+                                                }>
+                                                "Save & Fetch Models"
+                                            </button>
+                                        </div>
+
+                                        <div class="model-divider"></div>
+
+                                        {move || if let Some(err) = openai_models_error.get() {
+                                            view! { <div class="error-models">{err}</div> }.into_any()
+                                        } else if openai_models.get().is_empty() {
+                                            view! { <div class="no-models">"No models loaded yet"</div> }.into_any()
+                                        } else {
+                                            view! {
+                                                <div class="model-submenu">
+                                                    {openai_models.get().into_iter().map(|model| {
+                                                        let m_click = model.clone();
+                                                        let m_touch = model.clone();
+                                                        view! {
+                                                            <div class="model-option"
+                                                                 on:click=move |ev: web_sys::MouseEvent| {
+                                                                     ev.stop_propagation();
+                                                                     set_selected_model.set(Some(format!("openai:{}", m_click.clone())));
+                                                                     close_menus();
+                                                                 }
+                                                                 on:touchend=move |ev: web_sys::TouchEvent| {
+                                                                     ev.stop_propagation();
+                                                                     set_selected_model.set(Some(format!("openai:{}", m_touch.clone())));
+                                                                     close_menus();
+                                                                 }>
+                                                                {model}
+                                                            </div>
+                                                        }
+                                                    }).collect_view()}
+                                                </div>
+                                            }.into_any()
+                                        }}
+                                    </div>
+                                </div>
+
+                                // Registered remote Ollama servers - one runner-item each, scoped
+                                // to that server's own base_url for its model list, pulls and deletes.
+                                {move || remote_servers.get().into_iter().map(|server| {
+                                    let server_id = server.id.clone();
+                                    let server_for_panel = server.clone();
+                                    let server_for_panel2 = server.clone();
+                                    let server_for_panel3 = server.clone();
+                                    let server_for_panel4 = server.clone();
+                                    let server_for_remove = server.clone();
+                                    let server_for_models = server.clone();
+                                    let models_for_server = remote_models.get().get(&server.id).cloned().unwrap_or_default();
+                                    let is_open = move || remote_panel_open.get().as_ref() == Some(&server_id);
+
+                                    view! {
+                                        <div class="runner-item remote-runner"
+                                             on:mouseenter=move |ev: web_sys::MouseEvent| {
+                                                 ev.stop_propagation();
+                                                 set_remote_panel_open.set(Some(server_for_panel.id.clone()));
+                                             }
+                                             on:mouseleave=move |ev: web_sys::MouseEvent| {
+                                                 ev.stop_propagation();
+                                                 set_remote_panel_open.set(None);
+                                             }
+                                             on:click=move |ev: web_sys::MouseEvent| {
+                                                 ev.stop_propagation();
+                                                 set_remote_panel_open.set(Some(server_for_panel2.id.clone()));
+                                             }
+                                             on:touchstart=move |ev: web_sys::TouchEvent| {
+                                                 ev.stop_propagation();
+                                                 set_remote_panel_open.set(Some(server_for_panel3.id.clone()));
+                                             }>
+                                            <div class="runner-name">
+                                                {server.name.clone()}
+                                                <button class="remote-remove-btn"
+                                                        title="Forget this server"
+                                                        on:click=move |ev: web_sys::MouseEvent| {
+                                                            ev.stop_propagation();
+                                                            remove_remote_server_action(server_for_remove.id.clone());
+                                                        }>
+                                                    "✕"
+                                                </button>
+                                            </div>
+
+                                            <div class="models-panel remote-panel"
+                                                 class:hidden=move || !is_open()
+                                                 on:click=move |ev: web_sys::MouseEvent| ev.stop_propagation()>
+                                                <div class="add-model-section">
+                                                    {move || if show_add_remote_model.get().as_ref() == Some(&server_for_panel4.id) {
+                                                        let server_for_pull = server_for_panel4.clone();
+                                                        view! {
+                                                            <div class="add-model-input-row">
+                                                                <input
+                                                                    type="text"
+                                                                    class="add-model-input"
+                                                                    placeholder="model name (e.g. llama3)"
+                                                                    prop:value=move || new_remote_model_name.get()
+                                                                    on:input=move |ev| set_new_remote_model_name.set(event_target_value(&ev))
+                                                                    on:click=move |ev: web_sys::MouseEvent| ev.stop_propagation()
+                                                                    on:keydown={
+                                                                        let server = server_for_pull.clone();
+                                                                        move |ev: web_sys::KeyboardEvent| {
+                                                                            ev.stop_propagation();
+                                                                            if ev.key() == "Enter" {
+                                                                                start_remote_download(server.clone(), new_remote_model_name.get());
+                                                                            }
+                                                                        }
+                                                                    }
+                                                                />
+                                                                <button
+                                                                    class="add-model-btn pull-btn"
+                                                                    on:click=move |ev: web_sys::MouseEvent| {
+                                                                        ev.stop_propagation();
+                                                                        start_remote_download(server_for_pull.clone(), new_remote_model_name.get());
+                                                                    }
+                                                                >
+                                                                    "Pull"
+                                                                </button>
+                                                                <button
+                                                                    class="add-model-btn cancel-btn"
+                                                                    on:click=move |ev: web_sys::MouseEvent| {
+                                                                        ev.stop_propagation();
+                                                                        set_show_add_remote_model.set(None);
+                                                                        set_new_remote_model_name.set(String::new());
+                                                                    }
+                                                                >
+                                                                    "✕"
+                                                                </button>
+                                                            </div>
+                                                        }.into_any()
+                                                    } else {
+                                                        let id_for_add = server_for_panel4.id.clone();
+                                                        view! {
+                                                            <div class="model-option add-model-option"
+                                                                 on:click=move |ev: web_sys::MouseEvent| {
+                                                                     ev.stop_propagation();
+                                                                     set_show_add_remote_model.set(Some(id_for_add.clone()));
+                                                                 }>
+                                                                "+ Add Model"
+                                                            </div>
+                                                        }.into_any()
+                                                    }}
+                                                </div>
+
+                                                <div class="model-divider"></div>
+
+                                                {if models_for_server.is_empty() {
+                                                    view! { <div class="no-models">"No models yet"</div> }.into_any()
+                                                } else {
+                                                    view! {
+                                                        <div class="model-submenu">
+                                                            {models_for_server.into_iter().map(|model| {
+                                                                let m_click = model.clone();
+                                                                let m_touch = model.clone();
+                                                                let m_display = model.clone();
+                                                                let server_for_select = server_for_models.clone();
+                                                                let server_for_select2 = server_for_models.clone();
+                                                                let server_for_delete = server_for_models.clone();
+                                                                let delete_key = format!("{}:{}", server_for_models.id, model);
+                                                                let is_deleting = move || {
+                                                                    deleting_remote_model.get().as_ref() == Some(&delete_key)
+                                                                };
+                                                                view! {
+                                                                    <div class="model-option-row">
+                                                                        <div class="model-option"
+                                                                             on:click=move |ev: web_sys::MouseEvent| {
+                                                                                 ev.stop_propagation();
+                                                                                 select_model(format!("remote:{}:{}", server_for_select.id, m_click));
+                                                                             }
+                                                                             on:touchend=move |ev: web_sys::TouchEvent| {
+                                                                                 ev.stop_propagation();
+                                                                                 select_model(format!("remote:{}:{}", server_for_select2.id, m_touch));
+                                                                             }>
+                                                                            {m_display}
+                                                                        </div>
+                                                                        <button
+                                                                            class="model-delete-btn"
+                                                                            title="Delete model"
+                                                                            disabled=is_deleting()
+                                                                            on:click=move |ev: web_sys::MouseEvent| {
+                                                                                ev.stop_propagation();
+                                                                                set_confirm_delete.set(Some(PendingDelete::Remote(server_for_delete.clone(), model.clone())));
+                                                                            }>
+                                                                            {if is_deleting() { "..." } else { "❌" }}
+                                                                        </button>
+                                                                    </div>
+                                                                }
+                                                            }).collect_view()}
+                                                        </div>
+                                                    }.into_any()
+                                                }}
+                                            </div>
+                                        </div>
+                                    }
+                                }).collect_view()}
+
+                                // Add remote server runner item - name + base URL, then it joins the list above
+                                <div class="runner-item add-remote-runner"
+                                     on:mouseenter=move |ev: web_sys::MouseEvent| {
+                                         ev.stop_propagation();
+                                         set_show_add_remote.set(true);
+                                     }
+                                     on:mouseleave=move |ev: web_sys::MouseEvent| {
+                                         ev.stop_propagation();
+                                         set_show_add_remote.set(false);
+                                     }
+                                     on:click=move |ev: web_sys::MouseEvent| {
+                                         ev.stop_propagation();
+                                         set_show_add_remote.set(true);
+                                     }>
+                                    <div class="runner-name">"+ Remote Server"</div>
+
+                                    <div class="models-panel add-remote-panel"
+                                         class:hidden=move || !show_add_remote.get()
+                                         on:click=move |ev: web_sys::MouseEvent| ev.stop_propagation()>
+                                        <div class="add-model-section">
+                                            <input
+                                                type="text"
+                                                class="openai-config-input"
+                                                placeholder="Name (e.g. desktop)"
+                                                prop:value=move || new_remote_name.get()
+                                                on:input=move |ev| set_new_remote_name.set(event_target_value(&ev))
+                                                on:click=move |ev: web_sys::MouseEvent| ev.stop_propagation()
+                                                on:keydown=move |ev: web_sys::KeyboardEvent| ev.stop_propagation()
+                                            />
+                                            <input
+                                                type="text"
+                                                class="openai-config-input"
+                                                placeholder="Base URL (e.g. http://192.168.1.20:11434)"
+                                                prop:value=move || new_remote_url.get()
+                                                on:input=move |ev| set_new_remote_url.set(event_target_value(&ev))
+                                                on:click=move |ev: web_sys::MouseEvent| ev.stop_propagation()
+                                                on:keydown=move |ev: web_sys::KeyboardEvent| {
+                                                    ev.stop_propagation();
+                                                    if ev.key() == "Enter" {
+                                                        add_remote_server_action();
+                                                    }
+                                                }
+                                            />
+                                            <button
+                                                class="openai-save-btn"
+                                                on:click=move |ev: web_sys::MouseEvent| {
+                                                    ev.stop_propagation();
+                                                    add_remote_server_action();
+                                                }>
+                                                "Add Server"
+                                            </button>
+                                        </div>
                                     </div>
                                 </div>
                             </div>
@@ -1768,16 +5566,80 @@ pub fn App() -> impl IntoView {
                 </div>
 
                 <div class="header-right">
-                    <div class="status-dropdown">
-                        <button class="status-button"
+                    <div class="search-dropdown">
+                        <button class="search-toggle-button" title="Search conversation"
                                 on:click=move |ev: web_sys::MouseEvent| {
                                     ev.stop_propagation();
-                                    set_status_dropdown_open.update(|v| *v = !*v);
+                                    set_search_open.update(|open| *open = !*open);
+                                    if !search_open.get() {
+                                        set_search_query.set(String::new());
+                                    }
+                                }>
+                            "🔍"
+                        </button>
+                        <div class="search-panel"
+                             class:hidden=move || !search_open.get()
+                             on:click=move |ev: web_sys::MouseEvent| ev.stop_propagation()>
+                            <input
+                                type="text"
+                                class="search-input"
+                                placeholder="Search messages..."
+                                prop:value=move || search_query.get()
+                                on:input=move |ev| set_search_query.set(event_target_value(&ev))
+                                on:keydown=move |ev: web_sys::KeyboardEvent| {
+                                    if ev.key() == "Enter" {
+                                        ev.prevent_default();
+                                        goto_search_match(if ev.shift_key() { -1 } else { 1 });
+                                    } else if ev.key() == "Escape" {
+                                        set_search_open.set(false);
+                                        set_search_query.set(String::new());
+                                    }
+                                }
+                            />
+                            <span class="search-match-count">
+                                {move || {
+                                    let total = search_matches.get().len();
+                                    if search_query.get().trim().is_empty() {
+                                        String::new()
+                                    } else if total == 0 {
+                                        "0/0".to_string()
+                                    } else {
+                                        format!("{}/{}", search_current.get() + 1, total)
+                                    }
+                                }}
+                            </span>
+                            <button type="button" class="search-nav-btn" title="Previous match"
+                                    on:click=move |_| goto_search_match(-1)>
+                                "↑"
+                            </button>
+                            <button type="button" class="search-nav-btn" title="Next match"
+                                    on:click=move |_| goto_search_match(1)>
+                                "↓"
+                            </button>
+                            <button type="button" class="search-close-btn" title="Close search"
+                                    on:click=move |_| {
+                                        set_search_open.set(false);
+                                        set_search_query.set(String::new());
+                                    }>
+                                "✕"
+                            </button>
+                        </div>
+                    </div>
+                    <div class="status-dropdown">
+                        <button class="status-button"
+                                on:click={
+                                    let navigate = navigate.clone();
+                                    move |ev: web_sys::MouseEvent| {
+                                        ev.stop_propagation();
+                                        let now_open = !status_dropdown_open.get();
+                                        set_status_dropdown_open.set(now_open);
+                                        navigate(if now_open { "/settings" } else { "/" }, Default::default());
+                                    }
                                 }>
                             <span class="status-dot"
-                                  class:status-green=move || ollama_running.get() && !(brave_search_enabled.get() && brave_api_token.get().trim().is_empty())
+                                  class:status-green=move || ollama_running.get() && !(search_enabled.get() && search_provider_missing_credentials(&search_provider.get(), &search_credentials.get()))
                                   class:status-red=move || !ollama_running.get()
-                                  class:status-yellow=move || toggle_pending.get() || (brave_search_enabled.get() && brave_api_token.get().trim().is_empty())>
+                                  class:status-yellow=move || toggle_pending.get() || (search_enabled.get() && search_provider_missing_credentials(&search_provider.get(), &search_credentials.get()))>
                             </span>
                             "Status"
                         </button>
@@ -1799,23 +5661,24 @@ pub fn App() -> impl IntoView {
                                 </label>
                             </div>
 
-                            // Brave Search toggle with hover submenu
+                            // Web search toggle with hover submenu - provider and
+                            // credentials are generic, driven by `SearchProviderId`.
                             <div class="status-menu-item brave-search-item"
-                                 on:mouseenter=move |_| set_brave_submenu_open.set(true)
-                                 on:mouseleave=move |_| set_brave_submenu_open.set(false)>
+                                 on:mouseenter=move |_| set_search_submenu_open.set(true)
+                                 on:mouseleave=move |_| set_search_submenu_open.set(false)>
                                 <span class="status-label">"Web Search"</span>
                                 <label class="toggle-switch">
                                     <input type="checkbox"
-                                           id="brave-toggle"
-                                           prop:checked=move || brave_search_enabled.get()
+                                           id="search-toggle"
+                                           prop:checked=move || search_enabled.get()
                                            on:change=move |_| {
-                                               let new_val = !brave_search_enabled.get();
-                                               set_brave_search_enabled.set(new_val);
+                                               let new_val = !search_enabled.get();
+                                               set_search_enabled.set(new_val);
                                                #[cfg(target_arch = "wasm32")]
                                                {
                                                    if let Some(window) = web_sys::window() {
                                                        if let Ok(Some(storage)) = window.local_storage() {
-                                                           let _ = storage.set_item("brave_search_enabled", if new_val { "true" } else { "false" });
+                                                           let _ = storage.set_item("search_enabled", if new_val { "true" } else { "false" });
                                                        }
                                                    }
                                                }
@@ -1823,94 +5686,127 @@ pub fn App() -> impl IntoView {
                                     <span class="slider"></span>
                                 </label>
 
-                                // Brave Search submenu (appears on hover)
+                                // Search provider submenu (appears on hover)
                                 <div class="brave-submenu"
-                                     class:hidden=move || !brave_submenu_open.get()
-                                     on:mouseenter=move |_| set_brave_submenu_open.set(true)
-                                     on:mouseleave=move |_| set_brave_submenu_open.set(false)>
+                                     class:hidden=move || !search_submenu_open.get()
+                                     on:mouseenter=move |_| set_search_submenu_open.set(true)
+                                     on:mouseleave=move |_| set_search_submenu_open.set(false)>
                                     <div class="brave-submenu-content">
-                                        <div class="brave-submenu-header">"Brave Search API"</div>
-                                        <div class="brave-token-row">
-                                            <input
-                                                type="password"
-                                                class="brave-token-input"
-                                                placeholder="Enter API Token"
-                                                prop:value=move || brave_api_token.get()
-                                                on:input=move |ev| {
-                                                    let token = event_target_value(&ev);
-                                                    set_brave_api_token.set(token.clone());
-                                                    set_brave_test_status.set(None);
-                                                }
+                                        <div class="brave-submenu-header">"Web Search Provider"</div>
+                                        <select class="search-provider-select"
+                                                prop:value=move || search_provider.get()
                                                 on:click=move |ev: web_sys::MouseEvent| ev.stop_propagation()
-                                                on:keydown=move |ev: web_sys::KeyboardEvent| {
-                                                    ev.stop_propagation();
-                                                    if ev.key() == "Enter" {
-                                                        let token = brave_api_token.get();
-                                                        #[cfg(target_arch = "wasm32")]
-                                                        {
-                                                            if let Some(window) = web_sys::window() {
-                                                                if let Ok(Some(storage)) = window.local_storage() {
-                                                                    let _ = storage.set_item("brave_api_token", &token);
-                                                                }
+                                                on:change=move |ev| {
+                                                    let provider = event_target_value(&ev);
+                                                    set_search_provider.set(provider.clone());
+                                                    set_search_test_status.set(None);
+                                                    #[cfg(target_arch = "wasm32")]
+                                                    {
+                                                        if let Some(window) = web_sys::window() {
+                                                            if let Ok(Some(storage)) = window.local_storage() {
+                                                                let _ = storage.set_item("search_provider", &provider);
                                                             }
                                                         }
-                                                        set_brave_test_status.set(Some("Saved!".to_string()));
                                                     }
+                                                }>
+                                            {SearchProviderId::ALL.into_iter().map(|provider| {
+                                                view! {
+                                                    <option value=provider.key()>
+                                                        {provider.label()}
+                                                    </option>
                                                 }
-                                            />
-                                        </div>
+                                            }).collect_view()}
+                                        </select>
+
+                                        // Credential fields - one input per field the active provider declares.
+                                        {move || {
+                                            let provider = SearchProviderId::from_key(&search_provider.get());
+                                            provider.credential_fields().iter().map(|&(field_key, field_label, is_secret)| {
+                                                let storage_key = format!("search_cred.{}.{}", provider.key(), field_key);
+                                                let creds_key = storage_key.clone();
+                                                let creds_key_for_input = creds_key.clone();
+                                                view! {
+                                                    <div class="brave-token-row">
+                                                        <input
+                                                            type=if is_secret { "password" } else { "text" }
+                                                            class="brave-token-input"
+                                                            placeholder=field_label
+                                                            prop:value=move || search_credentials.get().get(&creds_key_for_input).cloned().unwrap_or_default()
+                                                            on:input={
+                                                                let creds_key = creds_key.clone();
+                                                                move |ev| {
+                                                                    let value = event_target_value(&ev);
+                                                                    set_search_credentials.update(|creds| { creds.insert(creds_key.clone(), value); });
+                                                                    set_search_test_status.set(None);
+                                                                }
+                                                            }
+                                                            on:click=move |ev: web_sys::MouseEvent| ev.stop_propagation()
+                                                        />
+                                                    </div>
+                                                }
+                                            }).collect_view()
+                                        }}
+
                                         <div class="brave-btn-row">
                                             <button
                                                 class="brave-save-btn"
                                                 on:click=move |ev: web_sys::MouseEvent| {
                                                     ev.stop_propagation();
-                                                    let token = brave_api_token.get();
+                                                    let provider = search_provider.get();
+                                                    let creds = search_credentials.get();
                                                     #[cfg(target_arch = "wasm32")]
                                                     {
                                                         if let Some(window) = web_sys::window() {
                                                             if let Ok(Some(storage)) = window.local_storage() {
-                                                                let _ = storage.set_item("brave_api_token", &token);
+                                                                let _ = storage.set_item("search_provider", &provider);
+                                                                for (field_key, _, _) in SearchProviderId::from_key(&provider).credential_fields() {
+                                                                    let key = format!("{}.{}", provider, field_key);
+                                                                    if let Some(value) = creds.get(&key) {
+                                                                        let _ = storage.set_item(&format!("search_cred.{}", key), value);
+                                                                    }
+                                                                }
                                                             }
                                                         }
                                                     }
-                                                    set_brave_test_status.set(Some("Saved!".to_string()));
+                                                    set_search_test_status.set(Some("Saved!".to_string()));
                                                 }>
                                                 "Save"
                                             </button>
                                             <button
                                                 class="brave-test-btn"
-                                                prop:disabled=move || brave_test_pending.get()
+                                                prop:disabled=move || search_test_pending.get()
                                                 on:click=move |ev: web_sys::MouseEvent| {
                                                     ev.stop_propagation();
-                                                    let token = brave_api_token.get();
-                                                    if token.trim().is_empty() {
-                                                        set_brave_test_status.set(Some("Enter token first".to_string()));
+                                                    let provider = search_provider.get();
+                                                    let creds = search_credentials.get();
+                                                    if search_provider_missing_credentials(&provider, &creds) {
+                                                        set_search_test_status.set(Some("Fill in the required fields first".to_string()));
                                                         return;
                                                     }
-                                                    set_brave_test_pending.set(true);
-                                                    set_brave_test_status.set(Some("Testing...".to_string()));
+                                                    set_search_test_pending.set(true);
+                                                    set_search_test_status.set(Some("Testing...".to_string()));
                                                     spawn_local(async move {
-                                                        match test_brave_api(token).await {
+                                                        match test_search_provider(provider, creds).await {
                                                             Ok(response) => {
                                                                 if response.success {
-                                                                    set_brave_test_status.set(Some("API working!".to_string()));
+                                                                    set_search_test_status.set(Some("API working!".to_string()));
                                                                 } else {
-                                                                    set_brave_test_status.set(Some(response.error.unwrap_or("Failed".to_string())));
+                                                                    set_search_test_status.set(Some(response.error.unwrap_or("Failed".to_string())));
                                                                 }
                                                             }
                                                             Err(e) => {
-                                                                set_brave_test_status.set(Some(format!("Error: {}", e)));
+                                                                set_search_test_status.set(Some(format!("Error: {}", e)));
                                                             }
                                                         }
-                                                        set_brave_test_pending.set(false);
+                                                        set_search_test_pending.set(false);
                                                     });
                                                 }>
-                                                {move || if brave_test_pending.get() { "..." } else { "Test" }}
+                                                {move || if search_test_pending.get() { "..." } else { "Test" }}
                                             </button>
                                         </div>
                                         // Status message
                                         {move || {
-                                            brave_test_status.get().map(|status| {
+                                            search_test_status.get().map(|status| {
                                                 let is_success = status.contains("working") || status.contains("Saved");
                                                 view! {
                                                     <div class="brave-status"
@@ -1921,7 +5817,7 @@ pub fn App() -> impl IntoView {
                                                 }
                                             })
                                         }}
-                                        <a href="https://brave.com/search/api/"
+                                        <a href=move || SearchProviderId::from_key(&search_provider.get()).docs_url().to_string()
                                            target="_blank"
                                            rel="noopener noreferrer"
                                            class="brave-api-link">
@@ -1933,6 +5829,113 @@ pub fn App() -> impl IntoView {
 
                             <div class="status-divider"></div>
 
+                            <div class="context-window-section">
+                                <div class="status-label">"Context Window (num_ctx)"</div>
+                                <input
+                                    type="number"
+                                    class="num-ctx-input"
+                                    min="512"
+                                    step="512"
+                                    prop:value=move || num_ctx.get().to_string()
+                                    on:change=move |ev| {
+                                        if let Ok(value) = event_target_value(&ev).parse::<u32>() {
+                                            set_num_ctx_override(value);
+                                        }
+                                    }
+                                />
+                            </div>
+
+                            <div class="status-divider"></div>
+
+                            <div class="profile-section">
+                                <div class="status-label">"Generation Profile"</div>
+                                <select
+                                    class="profile-select"
+                                    prop:value=move || active_profile_name.get().unwrap_or_default()
+                                    on:change=move |ev| {
+                                        let value = event_target_value(&ev);
+                                        set_active_profile_name.set(if value.is_empty() { None } else { Some(value) });
+                                    }
+                                >
+                                    <option value="">"Default"</option>
+                                    <For
+                                        each=move || model_profiles.get()
+                                        key=|p| p.name.clone()
+                                        children=move |p| view! { <option value=p.name.clone()>{p.name.clone()}</option> }
+                                    />
+                                </select>
+                                <div class="profile-actions">
+                                    <button class="profile-save-btn" on:click=move |_| set_show_save_profile.set(true)>
+                                        "Save as..."
+                                    </button>
+                                    {move || active_profile_name.get().map(|name| view! {
+                                        <button class="profile-delete-btn" on:click=move |_| {
+                                            let name = name.clone();
+                                            if let Some(model) = selected_model.get() {
+                                                spawn_local(async move {
+                                                    let _ = delete_model_profile(model.clone(), name).await;
+                                                    if let Ok(profiles) = list_model_profiles(model).await {
+                                                        set_model_profiles.set(profiles);
+                                                    }
+                                                    set_active_profile_name.set(None);
+                                                });
+                                            }
+                                        }>
+                                            "Delete"
+                                        </button>
+                                    })}
+                                </div>
+                                {move || if show_save_profile.get() {
+                                    view! {
+                                        <div class="profile-save-row">
+                                            <input
+                                                type="text"
+                                                class="profile-name-input"
+                                                placeholder="profile name (e.g. creative)"
+                                                prop:value=move || new_profile_name.get()
+                                                on:input=move |ev| set_new_profile_name.set(event_target_value(&ev))
+                                            />
+                                            <button
+                                                class="profile-save-btn"
+                                                on:click=move |_| {
+                                                    let name = new_profile_name.get();
+                                                    let Some(model) = selected_model.get() else { return };
+                                                    if name.trim().is_empty() {
+                                                        return;
+                                                    }
+                                                    let base = active_profile_name.get()
+                                                        .and_then(|n| model_profiles.get().into_iter().find(|p| p.name == n))
+                                                        .unwrap_or_default();
+                                                    let profile = ModelProfile {
+                                                        model: model.clone(),
+                                                        name: name.trim().to_string(),
+                                                        num_ctx: num_ctx.get(),
+                                                        ..base
+                                                    };
+                                                    spawn_local(async move {
+                                                        let saved_name = profile.name.clone();
+                                                        if save_model_profile(profile).await.is_ok() {
+                                                            if let Ok(profiles) = list_model_profiles(model).await {
+                                                                set_model_profiles.set(profiles);
+                                                            }
+                                                            set_active_profile_name.set(Some(saved_name));
+                                                        }
+                                                    });
+                                                    set_new_profile_name.set(String::new());
+                                                    set_show_save_profile.set(false);
+                                                }
+                                            >
+                                                "Save"
+                                            </button>
+                                        </div>
+                                    }.into_any()
+                                } else {
+                                    view! { "" }.into_any()
+                                }}
+                            </div>
+
+                            <div class="status-divider"></div>
+
                             <div class="theme-section">
                                 <div class="theme-label">"Theme"</div>
                                 <div class="theme-options">
@@ -1981,6 +5984,111 @@ pub fn App() -> impl IntoView {
                                         <span class="theme-dot nordic"></span>
                                         "Nordic"
                                     </div>
+                                    <div class="theme-option"
+                                         class:active=move || current_theme.get() == "custom"
+                                         on:click={
+                                             let apply = apply_theme.clone();
+                                             move |_| apply("custom".to_string())
+                                         }>
+                                        <span class="theme-dot custom"></span>
+                                        "Custom"
+                                    </div>
+                                </div>
+
+                                {move || (current_theme.get() == "custom").then(|| {
+                                    let colors = custom_theme_colors.get();
+                                    view! {
+                                        <div class="custom-theme-pickers">
+                                            <label class="custom-theme-picker">
+                                                "Background"
+                                                <input type="color" prop:value=colors.background.clone()
+                                                       on:input={
+                                                           let apply = apply_custom_color.clone();
+                                                           move |ev| apply("background", event_target_value(&ev))
+                                                       } />
+                                            </label>
+                                            <label class="custom-theme-picker">
+                                                "Surface"
+                                                <input type="color" prop:value=colors.surface.clone()
+                                                       on:input={
+                                                           let apply = apply_custom_color.clone();
+                                                           move |ev| apply("surface", event_target_value(&ev))
+                                                       } />
+                                            </label>
+                                            <label class="custom-theme-picker">
+                                                "Accent"
+                                                <input type="color" prop:value=colors.accent.clone()
+                                                       on:input={
+                                                           let apply = apply_custom_color.clone();
+                                                           move |ev| apply("accent", event_target_value(&ev))
+                                                       } />
+                                            </label>
+                                            <label class="custom-theme-picker">
+                                                "Text"
+                                                <input type="color" prop:value=colors.text.clone()
+                                                       on:input={
+                                                           let apply = apply_custom_color.clone();
+                                                           move |ev| apply("text", event_target_value(&ev))
+                                                       } />
+                                            </label>
+                                            <label class="custom-theme-picker">
+                                                "User bubble"
+                                                <input type="color" prop:value=colors.user_bubble.clone()
+                                                       on:input={
+                                                           let apply = apply_custom_color.clone();
+                                                           move |ev| apply("user_bubble", event_target_value(&ev))
+                                                       } />
+                                            </label>
+                                            <label class="custom-theme-picker">
+                                                "AI bubble"
+                                                <input type="color" prop:value=colors.ai_bubble.clone()
+                                                       on:input={
+                                                           let apply = apply_custom_color.clone();
+                                                           move |ev| apply("ai_bubble", event_target_value(&ev))
+                                                       } />
+                                            </label>
+                                            <button type="button" class="custom-theme-reset-btn"
+                                                    on:click=move |_| reset_custom_to_preset()>
+                                                "Reset to preset"
+                                            </button>
+                                        </div>
+                                    }
+                                })}
+                            </div>
+
+                            <div class="status-divider"></div>
+
+                            <div class="tts-section">
+                                <div class="tts-label">"Read Aloud"</div>
+                                <select class="tts-voice-select"
+                                        prop:value=move || tts_voice.get()
+                                        on:change={
+                                            let apply = apply_tts_voice.clone();
+                                            move |ev| apply(event_target_value(&ev))
+                                        }>
+                                    <option value="">"Default voice"</option>
+                                    {move || tts_voices.get().into_iter().map(|name| {
+                                        view! { <option value=name.clone()>{name}</option> }
+                                    }).collect_view()}
+                                </select>
+                                <div class="tts-rate-row">
+                                    <span class="tts-rate-label">{move || format!("Rate: {:.1}x", tts_rate.get())}</span>
+                                    <input
+                                        type="range"
+                                        class="tts-rate-slider"
+                                        min="0.5"
+                                        max="2"
+                                        step="0.1"
+                                        prop:value=move || tts_rate.get().to_string()
+                                        on:input={
+                                            let apply = apply_tts_rate.clone();
+                                            move |ev| {
+                                                if let Ok(rate) = event_target_value(&ev).parse::<f64>() {
+                                                    apply(rate);
+                                                }
+                                            }
+                                        }
+                                    />
                                 </div>
                             </div>
                         </div>
@@ -1988,11 +6096,175 @@ pub fn App() -> impl IntoView {
                 </div>
             </div>
 
-            // Backdrop for status dropdown
+            // Backdrop for status dropdown - navigate back to "/" on close,
+            // matching the status-button toggle's navigate("/settings", ...)
+            // on open, or a reload would reopen the settings panel.
             <div class="menu-backdrop"
                  class:hidden=move || !status_dropdown_open.get()
-                 on:click=move |_| set_status_dropdown_open.set(false)
-                 on:touchend=move |_| set_status_dropdown_open.set(false)>
+                 on:click={
+                     let navigate = navigate.clone();
+                     move |_| {
+                         set_status_dropdown_open.set(false);
+                         navigate("/", Default::default());
+                     }
+                 }
+                 on:touchend={
+                     let navigate = navigate.clone();
+                     move |_| {
+                         set_status_dropdown_open.set(false);
+                         navigate("/", Default::default());
+                     }
+                 }>
+            </div>
+
+            // Delete confirmation modal - guards the model-delete-btn (❌) so a
+            // mis-tap can't silently destroy a multi-gigabyte model. Covers both
+            // the local runner and registered remote servers.
+            <div class="delete-confirm-backdrop"
+                 class:hidden=move || confirm_delete.get().is_none()
+                 on:click=move |_| set_confirm_delete.set(None)>
+                <div class="delete-confirm-modal"
+                     on:click=move |ev: web_sys::MouseEvent| ev.stop_propagation()>
+                    <p class="delete-confirm-text">
+                        "Delete "
+                        <strong>{move || confirm_delete.get().map(|d| d.model_name().to_string()).unwrap_or_default()}</strong>
+                        "? This can't be undone."
+                    </p>
+                    <div class="delete-confirm-actions">
+                        <button class="delete-confirm-cancel"
+                                on:click=move |_| set_confirm_delete.set(None)>
+                            "Cancel"
+                        </button>
+                        <button class="delete-confirm-delete"
+                                on:click=move |_| {
+                                    if let Some(pending) = confirm_delete.get() {
+                                        match pending {
+                                            PendingDelete::Local(model) => do_delete_model(model),
+                                            PendingDelete::Remote(server, model) => do_delete_remote_model(server, model),
+                                        }
+                                    }
+                                    set_confirm_delete.set(None);
+                                }>
+                            "Delete"
+                        </button>
+                    </div>
+                </div>
+            </div>
+
+            // In-app model catalog browser - replaces linking out to
+            // ollama.com/library, which on Android opens a new tab and loses
+            // the user's place in the app.
+            <div class="catalog-backdrop"
+                 class:hidden=move || show_catalog.get().is_none()
+                 on:click=move |_| {
+                     set_show_catalog.set(None);
+                     set_catalog_search.set(String::new());
+                 }>
+                <div class="catalog-modal"
+                     on:click=move |ev: web_sys::MouseEvent| ev.stop_propagation()>
+                    <div class="catalog-header">
+                        <input
+                            type="text"
+                            class="catalog-search"
+                            placeholder="Search models..."
+                            prop:value=move || catalog_search.get()
+                            on:input=move |ev| set_catalog_search.set(event_target_value(&ev))
+                        />
+                        <button class="catalog-close"
+                                on:click=move |_| {
+                                    set_show_catalog.set(None);
+                                    set_catalog_search.set(String::new());
+                                }>
+                            "✕"
+                        </button>
+                    </div>
+                    <Suspense fallback=move || view! { <div class="loading-models">"Loading..."</div> }>
+                        <div class="catalog-list">
+                            {move || {
+                                let query = catalog_search.get().to_lowercase();
+                                catalog_resource.get().map(|result| {
+                                    match result {
+                                        Ok(entries) => {
+                                            let filtered: Vec<CatalogEntry> = entries.into_iter()
+                                                .filter(|e| query.is_empty() || e.name.to_lowercase().contains(&query))
+                                                .collect();
+                                            if filtered.is_empty() {
+                                                view! { <div class="no-models">"No matching models"</div> }.into_any()
+                                            } else {
+                                                view! {
+                                                    <div class="catalog-entries">
+                                                        {filtered.into_iter().map(|entry| {
+                                                            let pull_name = entry.name.clone();
+                                                            view! {
+                                                                <div class="catalog-entry">
+                                                                    <div class="catalog-entry-info">
+                                                                        <div class="catalog-entry-name">{entry.name}</div>
+                                                                        <div class="catalog-entry-description">{entry.description}</div>
+                                                                        <div class="catalog-entry-sizes">
+                                                                            {entry.sizes.into_iter().map(|size| {
+                                                                                view! { <span class="catalog-entry-size-tag">{size}</span> }
+                                                                            }).collect_view()}
+                                                                        </div>
+                                                                    </div>
+                                                                    <button class="catalog-entry-pull-btn pull-btn"
+                                                                            on:click=move |ev: web_sys::MouseEvent| {
+                                                                                ev.stop_propagation();
+                                                                                pull_from_catalog(pull_name.clone());
+                                                                            }>
+                                                                        "Pull"
+                                                                    </button>
+                                                                </div>
+                                                            }
+                                                        }).collect_view()}
+                                                    </div>
+                                                }.into_any()
+                                            }
+                                        }
+                                        Err(_) => view! { <div class="error-models">"Error loading catalog"</div> }.into_any()
+                                    }
+                                })
+                            }}
+                        </div>
+                    </Suspense>
+                </div>
+            </div>
+
+            // Full-screen media viewer - opened by tapping a message thumbnail.
+            // Pinch/drag pan-and-zoom is driven by pointer events on the whole
+            // overlay; a tap on the bare backdrop closes it, but releasing a
+            // pan/pinch gesture there must not (`media_viewer_moved` guards that).
+            <div class="media-viewer-backdrop"
+                 class:hidden=move || media_viewer_src.get().is_none()
+                 on:pointerdown=media_viewer_pointer_down
+                 on:pointermove=media_viewer_pointer_move
+                 on:pointerup=media_viewer_pointer_end
+                 on:pointercancel=media_viewer_pointer_end
+                 on:click=move |ev: web_sys::MouseEvent| {
+                     ev.stop_propagation();
+                     if media_viewer_moved.get() {
+                         set_media_viewer_moved.set(false);
+                     } else {
+                         set_media_viewer_src.set(None);
+                     }
+                 }>
+                <button class="media-viewer-close"
+                        on:click=move |ev: web_sys::MouseEvent| {
+                            ev.stop_propagation();
+                            set_media_viewer_src.set(None);
+                        }>
+                    "✕"
+                </button>
+                {move || media_viewer_src.get().map(|src| {
+                    let (offset_x, offset_y) = media_viewer_offset.get();
+                    let scale = media_viewer_scale.get();
+                    let transform = format!("translate({}px, {}px) scale({})", offset_x, offset_y, scale);
+                    view! {
+                        <img class="media-viewer-image"
+                             src=src
+                             style:transform=transform
+                             on:click=move |ev: web_sys::MouseEvent| ev.stop_propagation() />
+                    }
+                })}
             </div>
 
             // Download progress bars
@@ -2004,19 +6276,37 @@ pub fn App() -> impl IntoView {
                         .collect();
 
                     downloads.into_iter().map(|dl| {
-                        let model_name = dl.model.clone();
+                        // A remote-server entry is keyed as `remote:{server_id}:{model}`;
+                        // show just the model name alongside that server's label.
+                        let display_name = if let Some(rest) = dl.model.strip_prefix("remote:") {
+                            if let Some((server_id, model)) = rest.split_once(':') {
+                                let server_label = remote_servers.get().into_iter()
+                                    .find(|s| s.id == server_id)
+                                    .map(|s| s.name)
+                                    .unwrap_or_else(|| server_id.to_string());
+                                format!("{} ({})", model, server_label)
+                            } else {
+                                dl.model.clone()
+                            }
+                        } else {
+                            dl.model.clone()
+                        };
+                        let model_name = display_name;
                         let model_for_hide = dl.model.clone();
                         let model_for_cancel = dl.model.clone();
                         let model_for_cancel_update = dl.model.clone();
+                        let model_for_retry = dl.model.clone();
                         let status = dl.status.clone();
                         let status_for_check = status.clone();
                         let percent = dl.percent;
                         let speed = dl.speed.clone();
+                        let eta = dl.eta.clone();
                         let is_done = dl.done;
 
                         let is_complete = status_for_check == "Complete";
                         let is_cancelled = status_for_check == "Cancelled";
                         let can_cancel = !is_done && !is_complete && !is_cancelled;
+                        let can_retry = is_done && !is_complete;
 
                         view! {
                             <div class="download-progress-bar">
@@ -2031,6 +6321,11 @@ pub fn App() -> impl IntoView {
                                     } else {
                                         view! { <></> }.into_any()
                                     }}
+                                    {if !eta.is_empty() {
+                                        view! { <span class="download-eta">"ETA " {eta}</span> }.into_any()
+                                    } else {
+                                        view! { <></> }.into_any()
+                                    }}
                                     // Cancel button - stops the download
                                     {if can_cancel {
                                         view! {
@@ -2055,6 +6350,32 @@ pub fn App() -> impl IntoView {
                                     } else {
                                         view! { <></> }.into_any()
                                     }}
+                                    // Retry button - re-issues the pull for a failed/cancelled download
+                                    {if can_retry {
+                                        view! {
+                                            <button class="download-retry"
+                                                    title="Retry download"
+                                                    on:click=move |_| {
+                                                        let model = model_for_retry.clone();
+                                                        set_active_downloads.update(|downloads| {
+                                                            downloads.retain(|d| d.model != model);
+                                                        });
+                                                        if let Some(rest) = model.strip_prefix("remote:") {
+                                                            if let Some((server_id, bare_model)) = rest.split_once(':') {
+                                                                if let Some(server) = remote_servers.get().into_iter().find(|s| s.id == server_id) {
+                                                                    start_remote_download(server, bare_model.to_string());
+                                                                }
+                                                            }
+                                                        } else {
+                                                            start_download(model);
+                                                        }
+                                                    }>
+                                                "↻"
+                                            </button>
+                                        }.into_any()
+                                    } else {
+                                        view! { <></> }.into_any()
+                                    }}
                                     // Hide button - just removes from UI
                                     <button class="download-hide"
                                             title="Hide"
@@ -2080,18 +6401,32 @@ pub fn App() -> impl IntoView {
             // Chat window
             <div id="chat-window" class="chat-window">
                 <For
-                    each=move || messages.get()
-                    key=|msg| format!("{}-{}", msg.role, msg.text.len())
-                    children=move |msg| {
-                        let is_user = msg.role == "user";
-                        let is_empty_ai = msg.role == "ai" && msg.text.is_empty();
-                        let msg_text = msg.text.clone();
+                    each=move || {
+                        let all = messages.get();
+                        let last_index = all.len().saturating_sub(1);
+                        let streaming = is_streaming.get();
+                        all.into_iter()
+                            .enumerate()
+                            .filter(move |(i, msg)| {
+                                let m = msg.get();
+                                let is_blank = m.text.is_empty() && m.images.is_empty();
+                                !is_blank || (*i == last_index && streaming)
+                            })
+                            .collect::<Vec<_>>()
+                    }
+                    key=|(_, msg)| msg.get_untracked().id
+                    children=move |(index, msg)| {
+                        let is_user = move || msg.get().role == "user";
+                        let is_empty_ai = move || msg.get().role == "ai" && msg.get().text.is_empty();
+                        let bubble_msg_id = msg.get_untracked().id;
+                        let bubble_id = format!("msg-{}", bubble_msg_id);
 
                         view! {
-                            <div class="chat-bubble"
+                            <div id=bubble_id
+                                 class="chat-bubble"
                                  class:user-bubble=is_user
-                                 class:ai-bubble=!is_user>
-                                {if is_empty_ai {
+                                 class:ai-bubble=move || !is_user()>
+                                {move || if is_empty_ai() {
                                     // Thinking animation
                                     view! {
                                         <span class="thinking">
@@ -2109,12 +6444,42 @@ pub fn App() -> impl IntoView {
                                             </span>
                                         </span>
                                     }.into_any()
-                                } else if is_user {
-                                    // User message - plain text
-                                    view! { <span>{msg_text}</span> }.into_any()
+                                } else if is_user() {
+                                    // User message - escaped plain text (highlighted while
+                                    // searching), plus any attached images
+                                    view! {
+                                        <span inner_html=move || {
+                                            let escaped = escape_html(&msg.get().text);
+                                            if search_open.get() {
+                                                highlight_html(&escaped, &search_query.get())
+                                            } else {
+                                                escaped
+                                            }
+                                        }></span>
+                                        {move || (!msg.get().images.is_empty()).then(|| view! {
+                                            <div class="message-images">
+                                                {msg.get().images.into_iter().map(|src| {
+                                                    let src_for_click = src.clone();
+                                                    view! {
+                                                        <img class="message-image" src=src
+                                                             on:click=move |ev: web_sys::MouseEvent| {
+                                                                 ev.stop_propagation();
+                                                                 open_media_viewer(src_for_click.clone());
+                                                             } />
+                                                    }
+                                                }).collect_view()}
+                                            </div>
+                                        })}
+                                    }.into_any()
                                 } else {
-                                    // AI message with hostname prefix and markdown rendering
-                                    let rendered_html = markdown_to_html(&msg_text);
+                                    // AI message with hostname prefix, markdown rendering, and any
+                                    // images the model referenced in its response.
+                                    let rendered_html = markdown_to_html(&msg.get().text);
+                                    let rendered_html = if search_open.get() {
+                                        highlight_html(&rendered_html, &search_query.get())
+                                    } else {
+                                        rendered_html
+                                    };
                                     view! {
                                         <div class="ai-message-content">
                                             <span class="msg-prefix">
@@ -2125,9 +6490,79 @@ pub fn App() -> impl IntoView {
                                                 </Suspense>
                                             </span>
                                             <div class="markdown-content" inner_html=rendered_html></div>
+                                            {move || (!msg.get().images.is_empty()).then(|| view! {
+                                                <div class="message-images">
+                                                    {msg.get().images.into_iter().map(|src| {
+                                                        let src_for_click = src.clone();
+                                                        view! {
+                                                            <img class="message-image" src=src
+                                                                 on:click=move |ev: web_sys::MouseEvent| {
+                                                                     ev.stop_propagation();
+                                                                     open_media_viewer(src_for_click.clone());
+                                                                 } />
+                                                        }
+                                                    }).collect_view()}
+                                                </div>
+                                            })}
                                         </div>
                                     }.into_any()
                                 }}
+                                {move || (!is_empty_ai()).then(|| {
+                                    let reaction_list: Vec<(String, u32)> = msg.get().reactions.into_iter().collect();
+                                    view! {
+                                        <div class="message-actions">
+                                            <span class="message-reactions">
+                                                {reaction_list.into_iter().map(|(emoji, count)| {
+                                                    let emoji_for_remove = emoji.clone();
+                                                    let label = if count > 1 { format!("{} {}", emoji, count) } else { emoji.clone() };
+                                                    view! {
+                                                        <button type="button" class="reaction-pill" title="Remove reaction"
+                                                                on:click=move |_| remove_reaction(index, emoji_for_remove.clone())>
+                                                            {label}
+                                                        </button>
+                                                    }
+                                                }).collect_view()}
+                                                <button type="button" class="reaction-add-btn" title="Add reaction"
+                                                        on:click=move |ev: web_sys::MouseEvent| {
+                                                            ev.stop_propagation();
+                                                            set_reaction_picker_open.update(|open| {
+                                                                *open = if *open == Some(index) { None } else { Some(index) };
+                                                            });
+                                                        }>
+                                                    "+"
+                                                </button>
+                                                {move || (reaction_picker_open.get() == Some(index)).then(|| view! {
+                                                    <div class="reaction-picker">
+                                                        {REACTION_PALETTE.iter().map(|emoji| {
+                                                            let emoji = emoji.to_string();
+                                                            view! {
+                                                                <button type="button" class="reaction-picker-option"
+                                                                        on:click=move |ev: web_sys::MouseEvent| {
+                                                                            ev.stop_propagation();
+                                                                            add_reaction(index, emoji.clone());
+                                                                            set_reaction_picker_open.set(None);
+                                                                        }>
+                                                                    {emoji.clone()}
+                                                                </button>
+                                                            }
+                                                        }).collect_view()}
+                                                    </div>
+                                                })}
+                                            </span>
+                                            {(!is_user()).then(|| view! {
+                                                <button type="button" class="speak-btn" title="Read aloud"
+                                                        class:speaking=move || tts_speaking_id.get() == Some(bubble_msg_id)
+                                                        on:click=move |_| toggle_speak(bubble_msg_id, msg.get().text.clone())>
+                                                    {move || if tts_speaking_id.get() == Some(bubble_msg_id) { "⏹" } else { "🔊" }}
+                                                </button>
+                                                <button type="button" class="regenerate-btn" title="Regenerate"
+                                                        on:click=move |_| regenerate(index)>
+                                                    "↻"
+                                                </button>
+                                            })}
+                                        </div>
+                                    }
+                                })}
                             </div>
                         }
                     }
@@ -2136,6 +6571,115 @@ pub fn App() -> impl IntoView {
 
             // Input area
             <div class="chat-input-area">
+                {move || {
+                    interrupted_generation.get().map(|_| {
+                        let resume = resume_interrupted_generation;
+                        view! {
+                            <div class="error-boundary">
+                                <p class="error-message">"A response was interrupted before it finished - the device may have reloaded or backgrounded the app."</p>
+                                <div class="error-actions">
+                                    <button type="button" class="error-retry" on:click=move |_| resume()>"Resume"</button>
+                                    <button type="button" class="error-configure" on:click=move |_| {
+                                        set_interrupted_generation.set(None);
+                                        #[cfg(target_arch = "wasm32")]
+                                        clear_resumable_generation();
+                                    }>"Discard"</button>
+                                </div>
+                            </div>
+                        }
+                    })
+                }}
+                // A real `ErrorBoundary`, not a hand-rolled `Option` check - this
+                // only catches errors a reactive view actually reports through
+                // `Result::Err` (here, `chat_request_error`), same as
+                // upstream Leptos examples. It can't catch a raw panic (a
+                // malformed chunk, a `.unwrap()` in the streaming reader task
+                // spawned below): those happen outside the view-rendering
+                // graph entirely, so they still only reach the console via
+                // `console_error_panic_hook` in `lib.rs`, exactly as before.
+                <ErrorBoundary fallback=move |errors| {
+                    let retry = retry_last_send.clone();
+                    let navigate = navigate.clone();
+                    view! {
+                        <>
+                            {move || {
+                                let retry = retry.clone();
+                                let navigate = navigate.clone();
+                                errors.get().into_iter().find_map(|(_, error)| error.downcast_ref::<OllamaRequestError>().cloned()).map(|error| {
+                                    view! {
+                                        <ErrorTemplate
+                                            error=error
+                                            on_retry=move || retry()
+                                            on_configure=Some(move || {
+                                                set_status_dropdown_open.set(true);
+                                                navigate("/settings", Default::default());
+                                            })
+                                        />
+                                    }
+                                })
+                            }}
+                        </>
+                    }
+                }>
+                    {move || chat_request_error.get().map_or(Ok(()), Err)}
+                </ErrorBoundary>
+                {move || {
+                    let imgs = pending_images.get();
+                    (!imgs.is_empty()).then(|| view! {
+                        <div class="pending-images">
+                            {imgs.into_iter().enumerate().map(|(i, src)| {
+                                view! {
+                                    <div class="pending-image-thumb">
+                                        <img src=src />
+                                        <button type="button" class="remove-image-btn"
+                                            on:click=move |_| set_pending_images.update(|imgs| {
+                                                if i < imgs.len() { imgs.remove(i); }
+                                            })>
+                                            "✕"
+                                        </button>
+                                    </div>
+                                }
+                            }).collect_view()}
+                        </div>
+                    })
+                }}
+                <input
+                    id="image-picker"
+                    type="file"
+                    accept="image/*"
+                    multiple=true
+                    style="display: none"
+                    on:change=move |ev: web_sys::Event| {
+                        #[cfg(target_arch = "wasm32")]
+                        {
+                            use wasm_bindgen::JsCast;
+                            if let Some(input) = ev.target().and_then(|t| t.dyn_into::<web_sys::HtmlInputElement>().ok()) {
+                                if let Some(files) = input.files() {
+                                    handle_image_files(files);
+                                }
+                                input.set_value("");
+                            }
+                        }
+                    }
+                />
+                <button id="attach-image-button"
+                        type="button"
+                        on:click=move |_: web_sys::MouseEvent| {
+                            if let Some(document) = web_sys::window().and_then(|w| w.document()) {
+                                if let Some(el) = document.get_element_by_id("image-picker") {
+                                    #[cfg(target_arch = "wasm32")]
+                                    {
+                                        use wasm_bindgen::JsCast;
+                                        if let Some(el) = el.dyn_ref::<web_sys::HtmlElement>() {
+                                            el.click();
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        disabled=move || is_streaming.get()>
+                    "📎"
+                </button>
                 <textarea
                     id="prompt-input"
                     placeholder="Type your message..."
@@ -2159,5 +6703,6 @@ pub fn App() -> impl IntoView {
                 </button>
             </div>
         </div>
+        </Router>
     }
 }
@@ -0,0 +1,117 @@
+use leptos::prelude::*;
+use std::fmt;
+
+/// Classifies a failure talking to Ollama (or an OpenAI-compatible/remote
+/// backend behind the same `/api/stream` proxy) so the UI can decide whether
+/// it's worth an automatic reconnect or whether retrying the same request
+/// can never succeed without the user fixing something in settings first.
+#[derive(Clone, Debug, PartialEq)]
+pub enum OllamaRequestError {
+    /// `fetch` itself failed - nothing is listening at the configured address.
+    ConnectionRefused,
+    /// The server answered, but with a non-2xx status.
+    ServerError(u16),
+    /// The response stream ended mid-message instead of with a clean `done`.
+    StreamInterrupted,
+    /// A chunk didn't parse as the shape Ollama promises.
+    InvalidResponse(String),
+    /// The configured server URL (or similar setting) is wrong, so retrying
+    /// the same request won't help until the user changes it.
+    Configuration(String),
+}
+
+impl fmt::Display for OllamaRequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OllamaRequestError::ConnectionRefused => write!(f, "Can't reach the server"),
+            OllamaRequestError::ServerError(status) => write!(f, "Server returned an error (HTTP {status})"),
+            OllamaRequestError::StreamInterrupted => write!(f, "Connection was interrupted"),
+            OllamaRequestError::InvalidResponse(detail) => write!(f, "Unexpected response: {detail}"),
+            OllamaRequestError::Configuration(detail) => write!(f, "{detail}"),
+        }
+    }
+}
+
+impl std::error::Error for OllamaRequestError {}
+
+impl OllamaRequestError {
+    /// Transient errors are worth an automatic reconnect with backoff.
+    /// Configuration errors aren't - the same wrong URL will fail forever,
+    /// so those should send the user to settings instead of spinning.
+    pub fn is_transient(&self) -> bool {
+        !matches!(self, OllamaRequestError::Configuration(_))
+    }
+
+    /// Builds the right variant for a failed `fetch` (no response at all).
+    /// A base URL that isn't even a well-formed `http(s)://` address can
+    /// never connect, so that's reported as a configuration problem instead
+    /// of something a reconnect could fix.
+    pub fn from_fetch_failure(base_url: &str) -> Self {
+        if base_url.starts_with("http://") || base_url.starts_with("https://") {
+            OllamaRequestError::ConnectionRefused
+        } else {
+            OllamaRequestError::Configuration(format!(
+                "\"{base_url}\" isn't a valid server address - check it in settings"
+            ))
+        }
+    }
+
+    /// Classifies an in-band error message forwarded from the `/api/stream`
+    /// proxy (e.g. "no base URL configured", "no OLLAMA_API_KEY configured
+    /// for Ollama Cloud", "remote Ollama server not reachable") into the
+    /// right variant, since the proxy only ever sends plain text, not a
+    /// typed error.
+    pub fn from_stream_message(msg: &str) -> Self {
+        let lower = msg.to_lowercase();
+        if lower.contains("not configured")
+            || lower.contains("no base url")
+            || lower.contains("api_key")
+            || (lower.contains("no ") && lower.contains("configured"))
+        {
+            OllamaRequestError::Configuration(msg.to_string())
+        } else if lower.contains("not reachable") || lower.contains("unreachable") {
+            OllamaRequestError::ConnectionRefused
+        } else {
+            OllamaRequestError::InvalidResponse(msg.to_string())
+        }
+    }
+}
+
+/// Renders a caught `OllamaRequestError` as a retry-or-reconfigure panel
+/// instead of letting it surface as a console panic. `on_retry` re-runs the
+/// failed request; `on_configure` is only shown for configuration errors,
+/// since retrying those again would just repeat the same failure.
+#[component]
+pub fn ErrorTemplate<R, C>(
+    error: OllamaRequestError,
+    on_retry: R,
+    #[prop(optional)] on_configure: Option<C>,
+) -> impl IntoView
+where
+    R: Fn() + 'static,
+    C: Fn() + 'static,
+{
+    let is_transient = error.is_transient();
+    let message = error.to_string();
+
+    view! {
+        <div class="error-boundary">
+            <p class="error-message">{message}</p>
+            <div class="error-actions">
+                {if is_transient {
+                    view! {
+                        <button type="button" class="error-retry" on:click=move |_| on_retry()>"Reconnect"</button>
+                    }.into_any()
+                } else {
+                    view! { <></> }.into_any()
+                }}
+                {match on_configure {
+                    Some(on_configure) if !is_transient => view! {
+                        <button type="button" class="error-configure" on:click=move |_| on_configure()>"Open settings"</button>
+                    }.into_any(),
+                    _ => view! { <></> }.into_any(),
+                }}
+            </div>
+        </div>
+    }
+}
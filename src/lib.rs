@@ -1,4 +1,7 @@
 pub mod app;
+pub mod error_template;
+#[cfg(all(feature = "android", not(target_arch = "wasm32")))]
+pub mod android;
 
 use crate::app::*;
 use leptos::prelude::*;
@@ -1,8 +1,34 @@
+#[cfg(feature = "ssr")]
+#[derive(Clone)]
+pub struct OllamaConfig {
+    pub host: String,
+    pub cloud_url: String,
+    pub cloud_api_key: Option<String>,
+}
+
+#[cfg(feature = "ssr")]
+impl OllamaConfig {
+    fn from_env() -> Self {
+        Self {
+            host: std::env::var("OLLAMA_HOST").unwrap_or_else(|_| "http://localhost:11434".to_string()),
+            cloud_url: std::env::var("OLLAMA_CLOUD_URL").unwrap_or_else(|_| "https://api.ollama.com".to_string()),
+            cloud_api_key: std::env::var("OLLAMA_API_KEY").ok(),
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Clone, axum::extract::FromRef)]
+pub struct AppState {
+    pub leptos_options: leptos::prelude::LeptosOptions,
+    pub ollama: OllamaConfig,
+}
+
 #[cfg(feature = "ssr")]
 #[tokio::main]
 async fn main() {
     use ollama_rust::app::*;
-    use axum::routing::post;
+    use axum::routing::{get, post};
     use axum::Router;
     use leptos::prelude::*;
     use leptos_axum::{generate_route_list, LeptosRoutes};
@@ -12,31 +38,247 @@ async fn main() {
     let addr = conf.leptos_options.site_addr;
     let leptos_options = conf.leptos_options;
     let routes = generate_route_list(App);
+    let state = AppState {
+        leptos_options: leptos_options.clone(),
+        ollama: OllamaConfig::from_env(),
+    };
 
     let app = Router::new()
         .route("/api/stream", post(stream_handler))
+        .route("/api/models", get(models_handler))
+        .route("/api/embed", post(embed_handler))
+        .route("/api/events", post(events_handler))
         .nest_service("/pkg", ServeDir::new(format!("{}/pkg", &leptos_options.site_root)).append_index_html_on_directories(false))
         .leptos_routes(&leptos_options, routes, {
             let leptos_options = leptos_options.clone();
             move || shell(leptos_options.clone())
         })
-        .with_state(leptos_options);
+        .with_state(state);
 
     let listener = tokio::net::TcpListener::bind(&addr).await.unwrap();
     leptos::logging::log!("listening on http://{}", &addr);
     axum::serve(listener, app).await.unwrap();
 }
 
+#[cfg(feature = "ssr")]
+const DEFAULT_NUM_CTX: u32 = 4096;
+
+/// Longest edge (px) an attached image is downscaled to before it's sent to
+/// Ollama. Android camera captures can be tens of megapixels, which would
+/// blow both the model's context budget and the mobile memory budget if
+/// forwarded untouched.
+#[cfg(feature = "ssr")]
+const MAX_IMAGE_EDGE: u32 = 1024;
+
+#[cfg(feature = "ssr")]
+fn base64_decode(input: &str) -> Vec<u8> {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut table = [255u8; 256];
+    for (i, &c) in ALPHABET.iter().enumerate() {
+        table[c as usize] = i as u8;
+    }
+    let mut bits: u32 = 0;
+    let mut bit_count = 0u32;
+    let mut out = Vec::new();
+    for c in input.bytes() {
+        if c == b'=' {
+            break;
+        }
+        let val = table[c as usize];
+        if val == 255 {
+            continue;
+        }
+        bits = (bits << 6) | val as u32;
+        bit_count += 6;
+        if bit_count >= 8 {
+            bit_count -= 8;
+            out.push((bits >> bit_count) as u8);
+        }
+    }
+    out
+}
+
+#[cfg(feature = "ssr")]
+fn base64_encode(input: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(input.len().div_ceil(3) * 4);
+    for chunk in input.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = chunk.get(1).copied().unwrap_or(0);
+        let b2 = chunk.get(2).copied().unwrap_or(0);
+        out.push(ALPHABET[(b0 >> 2) as usize] as char);
+        out.push(ALPHABET[(((b0 & 0x03) << 4) | (b1 >> 4)) as usize] as char);
+        out.push(if chunk.len() > 1 { ALPHABET[(((b1 & 0x0f) << 2) | (b2 >> 6)) as usize] as char } else { '=' });
+        out.push(if chunk.len() > 2 { ALPHABET[(b2 & 0x3f) as usize] as char } else { '=' });
+    }
+    out
+}
+
+/// Decodes a (possibly data-URL-prefixed) base64 image, downscales it so its
+/// longest edge is at most `MAX_IMAGE_EDGE` px, and re-encodes it as base64
+/// PNG. Falls back to the original bytes, re-encoded, if decoding or encoding
+/// the downscaled image ever fails.
+#[cfg(feature = "ssr")]
+fn downscale_image_base64(data: &str) -> String {
+    let raw = data.split(',').next_back().unwrap_or(data);
+    let bytes = base64_decode(raw);
+
+    let Ok(img) = image::load_from_memory(&bytes) else {
+        return base64_encode(&bytes);
+    };
+    if img.width() <= MAX_IMAGE_EDGE && img.height() <= MAX_IMAGE_EDGE {
+        return base64_encode(&bytes);
+    }
+
+    let resized = img.resize(MAX_IMAGE_EDGE, MAX_IMAGE_EDGE, image::imageops::FilterType::Lanczos3);
+    let mut out = Vec::new();
+    if resized.write_to(&mut std::io::Cursor::new(&mut out), image::ImageFormat::Png).is_err() {
+        return base64_encode(&bytes);
+    }
+    base64_encode(&out)
+}
+
+/// Turns one line of Ollama's NDJSON chat stream into the SSE events to forward:
+/// a "generating" transition on the first token, a data chunk, an error event,
+/// a `tool_calls` event, and/or a stats event followed by `__END__`. `generating` tracks whether the
+/// first non-empty token has been seen yet for this stream.
+#[cfg(feature = "ssr")]
+fn chat_line_events(json: &serde_json::Value, generating: &mut bool) -> Vec<axum::response::sse::Event> {
+    let mut events = Vec::new();
+
+    if let Some(error) = json["error"].as_str() {
+        events.push(axum::response::sse::Event::default().event("error").data(error));
+    }
+
+    if let Some(calls) = json["message"]["tool_calls"].as_array() {
+        if !calls.is_empty() {
+            events.push(axum::response::sse::Event::default().event("tool_calls").data(serde_json::Value::from(calls.clone()).to_string()));
+        }
+    }
+
+    if let Some(text) = json["message"]["content"].as_str() {
+        if !text.is_empty() {
+            if !*generating {
+                *generating = true;
+                events.push(axum::response::sse::Event::default().event("generating").data(""));
+            }
+            events.push(axum::response::sse::Event::default().data(text));
+        }
+    }
+
+    if json["done"].as_bool().unwrap_or(false) {
+        let stats = serde_json::json!({
+            "total_duration": json["total_duration"],
+            "eval_count": json["eval_count"],
+            "prompt_eval_count": json["prompt_eval_count"],
+        });
+        events.push(axum::response::sse::Event::default().event("stats").data(stats.to_string()));
+        events.push(axum::response::sse::Event::default().data("__END__"));
+    }
+
+    events
+}
+
+/// Turns one `data:` line of an OpenAI-compatible chat completions stream
+/// into the SSE events to forward. Mirrors `chat_line_events`, but follows
+/// the OpenAI wire format (`choices[0].delta.content`, a `[DONE]` sentinel)
+/// instead of Ollama's NDJSON.
+#[cfg(feature = "ssr")]
+fn openai_chat_line_events(data: &str, generating: &mut bool) -> Vec<axum::response::sse::Event> {
+    let mut events = Vec::new();
+
+    if data == "[DONE]" {
+        events.push(axum::response::sse::Event::default().event("stats").data("{}"));
+        events.push(axum::response::sse::Event::default().data("__END__"));
+        return events;
+    }
+
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(data) else {
+        return events;
+    };
+
+    if let Some(error) = json["error"]["message"].as_str() {
+        events.push(axum::response::sse::Event::default().event("error").data(error));
+    }
+
+    if let Some(text) = json["choices"][0]["delta"]["content"].as_str() {
+        if !text.is_empty() {
+            if !*generating {
+                *generating = true;
+                events.push(axum::response::sse::Event::default().event("generating").data(""));
+            }
+            events.push(axum::response::sse::Event::default().data(text));
+        }
+    }
+
+    events
+}
+
+#[cfg(feature = "ssr")]
+#[derive(serde::Deserialize)]
+pub struct ChatTurn {
+    pub role: String,
+    pub content: String,
+    /// Base64 (data-URL) image attachments for vision-capable models.
+    #[serde(default)]
+    pub images: Vec<String>,
+}
+
+#[cfg(feature = "ssr")]
+#[derive(serde::Deserialize, Default)]
+pub struct PromptOptions {
+    pub num_ctx: Option<u32>,
+    pub temperature: Option<f32>,
+    pub top_p: Option<f32>,
+    pub repeat_penalty: Option<f32>,
+    #[serde(default)]
+    pub stop: Vec<String>,
+}
+
 #[cfg(feature = "ssr")]
 #[derive(serde::Deserialize)]
 pub struct PromptRequest {
     pub model: String,
-    pub prompt: String,
+    pub messages: Vec<ChatTurn>,
+    #[serde(default)]
+    pub options: PromptOptions,
+    /// Ollama tool schema for agentic tool-calling (e.g. a `web_search` function).
+    #[serde(default)]
+    pub tools: Option<Vec<serde_json::Value>>,
+    /// Base URL and API key for an `openai:`-prefixed model, configured
+    /// client-side per request rather than via server env (unlike the
+    /// `cloud:` runner's `OLLAMA_API_KEY`).
+    #[serde(default)]
+    pub openai_base_url: Option<String>,
+    #[serde(default)]
+    pub openai_api_key: Option<String>,
+    /// Base URL for a `remote:{server_id}:{model}`-prefixed model, resolved
+    /// client-side from the registered server list and sent per request -
+    /// same rationale as `openai_base_url`, since the registry lives in
+    /// browser-visible app state rather than `AppState`.
+    #[serde(default)]
+    pub remote_base_url: Option<String>,
+}
+
+/// Layers the sampling params a saved `ModelProfile` contributes (beyond the
+/// always-present `num_ctx`/`temperature`) onto an Ollama `options` object,
+/// omitting each field the client didn't send rather than forwarding `null`.
+#[cfg(feature = "ssr")]
+fn merge_sampling_options(options: &mut serde_json::Value, opts: &PromptOptions) {
+    if let Some(top_p) = opts.top_p {
+        options["top_p"] = serde_json::Value::from(top_p);
+    }
+    if let Some(repeat_penalty) = opts.repeat_penalty {
+        options["repeat_penalty"] = serde_json::Value::from(repeat_penalty);
+    }
+    if !opts.stop.is_empty() {
+        options["stop"] = serde_json::Value::from(opts.stop.clone());
+    }
 }
 
 #[cfg(feature = "ssr")]
 async fn stream_handler(
-    axum::extract::State(_state): axum::extract::State<leptos::prelude::LeptosOptions>,
+    axum::extract::State(config): axum::extract::State<OllamaConfig>,
     axum::Json(payload): axum::Json<PromptRequest>,
 ) -> axum::response::sse::Sse<std::pin::Pin<Box<dyn futures::Stream<Item = Result<axum::response::sse::Event, std::convert::Infallible>> + Send>>> {
     use futures::StreamExt;
@@ -45,43 +287,274 @@ async fn stream_handler(
 
     // Check if this is a cloud model request
     if payload.model.starts_with("cloud:") {
-        let cloud_model = payload.model.strip_prefix("cloud:").unwrap_or(&payload.model);
-
-        // For demo purposes, simulate a cloud model response
-        // In production, this would call the actual Ollama Cloud API
-        let response_text = format!(
-            "[Cloud Demo] You asked: \"{}\"\n\n\
-            This is a simulated response from cloud model '{}'. \
-            In a production environment, this would connect to the actual Ollama Cloud API \
-            to process your request using cloud-hosted models.\n\n\
-            To use real cloud models, you'll need to:\n\
-            1. Sign up for Ollama Cloud at ollama.com\n\
-            2. Get your API credentials\n\
-            3. Configure the cloud endpoint in your settings",
-            payload.prompt.chars().take(100).collect::<String>(),
-            cloud_model
-        );
-
-        let stream = async_stream::stream! {
-            // Stream the response word by word for a more realistic effect
-            for word in response_text.split_whitespace() {
-                yield Ok(axum::response::sse::Event::default().data(format!("{} ", word)));
-                tokio::time::sleep(tokio::time::Duration::from_millis(30)).await;
+        let cloud_model = payload.model.strip_prefix("cloud:").unwrap_or(&payload.model).to_string();
+
+        let Some(api_key) = config.cloud_api_key.clone() else {
+            let error_stream = futures::stream::once(async {
+                Ok(axum::response::sse::Event::default()
+                    .data("[Error: no OLLAMA_API_KEY configured for Ollama Cloud]"))
+            });
+            return axum::response::sse::Sse::new(Box::pin(error_stream));
+        };
+
+        let num_ctx = payload.options.num_ctx.unwrap_or(DEFAULT_NUM_CTX);
+        let messages: Vec<serde_json::Value> = payload.messages.iter()
+            .map(|m| {
+                let mut turn = serde_json::json!({ "role": m.role, "content": m.content });
+                if !m.images.is_empty() {
+                    turn["images"] = serde_json::Value::from(m.images.iter().map(|img| downscale_image_base64(img)).collect::<Vec<_>>());
+                }
+                turn
+            })
+            .collect();
+
+        let mut body = serde_json::json!({
+            "model": cloud_model,
+            "messages": messages,
+            "stream": true,
+            "options": {
+                "num_ctx": num_ctx,
+                "temperature": payload.options.temperature,
+            }
+        });
+        merge_sampling_options(&mut body["options"], &payload.options);
+        if let Some(tools) = &payload.tools {
+            body["tools"] = serde_json::Value::from(tools.clone());
+        }
+
+        let client = reqwest::Client::new();
+        let res = client
+            .post(format!("{}/api/chat", config.cloud_url))
+            .bearer_auth(api_key)
+            .json(&body)
+            .send()
+            .await;
+
+        return match res {
+            Ok(response) => {
+                let body_with_io_error = response.bytes_stream().map(|res| {
+                    res.map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
+                });
+                let reader = StreamReader::new(body_with_io_error);
+                let mut lines = FramedRead::new(reader, LinesCodec::new());
+
+                let model = cloud_model.clone();
+                let stream = async_stream::stream! {
+                    yield Ok(axum::response::sse::Event::default().event("loading").data(model));
+                    let mut generating = false;
+                    while let Some(Ok(line)) = lines.next().await {
+                        if let Ok(json) = serde_json::from_str::<serde_json::Value>(&line) {
+                            for event in chat_line_events(&json, &mut generating) {
+                                yield Ok(event);
+                            }
+                        }
+                    }
+                };
+                axum::response::sse::Sse::new(Box::pin(stream))
+            }
+            Err(_) => {
+                let error_stream = futures::stream::once(async {
+                    Ok(axum::response::sse::Event::default().data("[Error: Ollama Cloud not reachable]"))
+                });
+                axum::response::sse::Sse::new(Box::pin(error_stream))
+            }
+        };
+    }
+
+    // OpenAI-compatible model request (base URL + key supplied per request
+    // from client-side localStorage, not server config)
+    if payload.model.starts_with("openai:") {
+        let openai_model = payload.model.strip_prefix("openai:").unwrap_or(&payload.model).to_string();
+        let base_url = payload.openai_base_url.clone().unwrap_or_default();
+        let api_key = payload.openai_api_key.clone().unwrap_or_default();
+
+        if base_url.trim().is_empty() {
+            let error_stream = futures::stream::once(async {
+                Ok(axum::response::sse::Event::default()
+                    .data("[Error: no base URL configured for the OpenAI-compatible runner]"))
+            });
+            return axum::response::sse::Sse::new(Box::pin(error_stream));
+        }
+        let Some(client) = ollama_rust::app::safe_proxy_client(base_url.trim()).await else {
+            let error_stream = futures::stream::once(async {
+                Ok(axum::response::sse::Event::default()
+                    .data("[Error: this base URL is not allowed]"))
+            });
+            return axum::response::sse::Sse::new(Box::pin(error_stream));
+        };
+
+        let messages: Vec<serde_json::Value> = payload.messages.iter()
+            .map(|m| serde_json::json!({ "role": m.role, "content": m.content }))
+            .collect();
+
+        let body = serde_json::json!({
+            "model": openai_model,
+            "messages": messages,
+            "stream": true,
+            "temperature": payload.options.temperature,
+        });
+
+        let base = base_url.trim().trim_end_matches('/').to_string();
+        let mut req = client.post(format!("{}/chat/completions", base));
+        if !api_key.trim().is_empty() {
+            req = req.bearer_auth(api_key.trim());
+        }
+        let res = req.json(&body).send().await;
+
+        return match res {
+            Ok(response) => {
+                let body_with_io_error = response.bytes_stream().map(|res| {
+                    res.map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
+                });
+                let reader = StreamReader::new(body_with_io_error);
+                let mut lines = FramedRead::new(reader, LinesCodec::new());
+
+                let model = openai_model.clone();
+                let stream = async_stream::stream! {
+                    yield Ok(axum::response::sse::Event::default().event("loading").data(model));
+                    let mut generating = false;
+                    while let Some(Ok(line)) = lines.next().await {
+                        let Some(data) = line.strip_prefix("data: ") else { continue };
+                        for event in openai_chat_line_events(data.trim(), &mut generating) {
+                            yield Ok(event);
+                        }
+                    }
+                };
+                axum::response::sse::Sse::new(Box::pin(stream))
+            }
+            Err(_) => {
+                let error_stream = futures::stream::once(async {
+                    Ok(axum::response::sse::Event::default().data("[Error: OpenAI-compatible server not reachable]"))
+                });
+                axum::response::sse::Sse::new(Box::pin(error_stream))
+            }
+        };
+    }
+
+    // Remote Ollama server request (user-registered endpoint, not the local
+    // runner) - routed by the base URL the client resolved from its own
+    // registered-server list, just like the `openai:` branch above.
+    if payload.model.starts_with("remote:") {
+        let rest = payload.model.strip_prefix("remote:").unwrap_or(&payload.model);
+        let remote_model = rest.split_once(':').map(|(_, m)| m.to_string()).unwrap_or_else(|| rest.to_string());
+        let base_url = payload.remote_base_url.clone().unwrap_or_default();
+
+        if base_url.trim().is_empty() {
+            let error_stream = futures::stream::once(async {
+                Ok(axum::response::sse::Event::default()
+                    .data("[Error: no base URL configured for this remote server]"))
+            });
+            return axum::response::sse::Sse::new(Box::pin(error_stream));
+        }
+        // Only ever proxy to a server the user actually registered through
+        // `add_remote_server` - otherwise a client could point this process
+        // at an arbitrary host it never agreed to talk to.
+        let client = if ollama_rust::app::is_registered_remote_base_url(base_url.trim()) {
+            ollama_rust::app::safe_proxy_client(base_url.trim()).await
+        } else {
+            None
+        };
+        let Some(client) = client else {
+            let error_stream = futures::stream::once(async {
+                Ok(axum::response::sse::Event::default()
+                    .data("[Error: this remote server is not registered]"))
+            });
+            return axum::response::sse::Sse::new(Box::pin(error_stream));
+        };
+
+        let num_ctx = payload.options.num_ctx.unwrap_or(DEFAULT_NUM_CTX);
+        let messages: Vec<serde_json::Value> = payload.messages.iter()
+            .map(|m| {
+                let mut turn = serde_json::json!({ "role": m.role, "content": m.content });
+                if !m.images.is_empty() {
+                    turn["images"] = serde_json::Value::from(m.images.iter().map(|img| downscale_image_base64(img)).collect::<Vec<_>>());
+                }
+                turn
+            })
+            .collect();
+
+        let mut body = serde_json::json!({
+            "model": remote_model,
+            "messages": messages,
+            "stream": true,
+            "options": {
+                "num_ctx": num_ctx,
+                "temperature": payload.options.temperature,
+            }
+        });
+        merge_sampling_options(&mut body["options"], &payload.options);
+        if let Some(tools) = &payload.tools {
+            body["tools"] = serde_json::Value::from(tools.clone());
+        }
+
+        let res = client
+            .post(format!("{}/api/chat", base_url.trim().trim_end_matches('/')))
+            .json(&body)
+            .send()
+            .await;
+
+        return match res {
+            Ok(response) => {
+                let body_with_io_error = response.bytes_stream().map(|res| {
+                    res.map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
+                });
+                let reader = StreamReader::new(body_with_io_error);
+                let mut lines = FramedRead::new(reader, LinesCodec::new());
+
+                let model = remote_model.clone();
+                let stream = async_stream::stream! {
+                    yield Ok(axum::response::sse::Event::default().event("loading").data(model));
+                    let mut generating = false;
+                    while let Some(Ok(line)) = lines.next().await {
+                        if let Ok(json) = serde_json::from_str::<serde_json::Value>(&line) {
+                            for event in chat_line_events(&json, &mut generating) {
+                                yield Ok(event);
+                            }
+                        }
+                    }
+                };
+                axum::response::sse::Sse::new(Box::pin(stream))
+            }
+            Err(_) => {
+                let error_stream = futures::stream::once(async {
+                    Ok(axum::response::sse::Event::default().data("[Error: remote Ollama server not reachable]"))
+                });
+                axum::response::sse::Sse::new(Box::pin(error_stream))
             }
-            yield Ok(axum::response::sse::Event::default().data("__END__"));
         };
-        return axum::response::sse::Sse::new(Box::pin(stream));
     }
 
     // Local Ollama model request
-    let client = reqwest::Client::new();
+    let num_ctx = payload.options.num_ctx.unwrap_or(DEFAULT_NUM_CTX);
+    let messages: Vec<serde_json::Value> = payload.messages.iter()
+        .map(|m| {
+            let mut turn = serde_json::json!({ "role": m.role, "content": m.content });
+            if !m.images.is_empty() {
+                turn["images"] = serde_json::Value::from(m.images.iter().map(|img| downscale_image_base64(img)).collect::<Vec<_>>());
+            }
+            turn
+        })
+        .collect();
+
+    let mut body = serde_json::json!({
+        "model": payload.model,
+        "messages": messages,
+        "stream": true,
+        "options": {
+            "num_ctx": num_ctx,
+            "temperature": payload.options.temperature,
+        }
+    });
+    merge_sampling_options(&mut body["options"], &payload.options);
+    if let Some(tools) = &payload.tools {
+        body["tools"] = serde_json::Value::from(tools.clone());
+    }
+
+    let endpoint = ollama_rust::app::get_ollama_endpoint_store().lock().unwrap().clone();
+    let client = ollama_rust::app::ollama_client(&endpoint);
     let res = client
-        .post("http://localhost:11434/api/generate")
-        .json(&serde_json::json!({
-            "model": payload.model,
-            "prompt": payload.prompt,
-            "stream": true
-        }))
+        .post(format!("{}/api/chat", endpoint.base_url()))
+        .json(&body)
         .send()
         .await;
 
@@ -93,14 +566,31 @@ async fn stream_handler(
             let reader = StreamReader::new(body_with_io_error);
             let mut lines = FramedRead::new(reader, LinesCodec::new());
 
+            let model = payload.model.clone();
             let stream = async_stream::stream! {
+                // Holds a partial wake-lock and an ongoing, cancellable
+                // notification for as long as this generator is alive, so the
+                // generation survives the WebView being backgrounded. Dropped
+                // (tearing both down) whether the stream finishes, errors, or
+                // the client disconnects mid-generation.
+                #[cfg(feature = "android")]
+                let generation_guard = ollama_rust::android::GenerationGuard::start(&model);
+
+                yield Ok(axum::response::sse::Event::default().event("loading").data(model));
+                let mut generating = false;
+                #[cfg_attr(not(feature = "android"), allow(unused_mut))]
+                let mut tokens_streamed: u32 = 0;
                 while let Some(Ok(line)) = lines.next().await {
                     if let Ok(json) = serde_json::from_str::<serde_json::Value>(&line) {
-                        if let Some(text) = json["response"].as_str() {
-                            yield Ok(axum::response::sse::Event::default().data(text));
+                        if json["message"]["content"].as_str().is_some_and(|t| !t.is_empty()) {
+                            tokens_streamed += 1;
+                            #[cfg(feature = "android")]
+                            if tokens_streamed % 20 == 0 {
+                                generation_guard.update_progress(tokens_streamed);
+                            }
                         }
-                        if json["done"].as_bool().unwrap_or(false) {
-                            yield Ok(axum::response::sse::Event::default().data("__END__"));
+                        for event in chat_line_events(&json, &mut generating) {
+                            yield Ok(event);
                         }
                     }
                 }
@@ -116,5 +606,148 @@ async fn stream_handler(
     }
 }
 
+#[cfg(feature = "ssr")]
+#[derive(serde::Serialize)]
+pub struct ModelInfo {
+    pub name: String,
+    pub size: u64,
+    pub modified_at: String,
+    pub parameter_size: String,
+    pub quantization_level: String,
+}
+
+#[cfg(feature = "ssr")]
+async fn models_handler() -> axum::response::Response {
+    use axum::response::IntoResponse;
+
+    let endpoint = ollama_rust::app::get_ollama_endpoint_store().lock().unwrap().clone();
+    let client = ollama_rust::app::ollama_client(&endpoint);
+    let res = client.get(format!("{}/api/tags", endpoint.base_url())).send().await;
+
+    let json = match res {
+        Ok(response) => response.json::<serde_json::Value>().await.ok(),
+        Err(_) => None,
+    };
+
+    let Some(json) = json else {
+        return (
+            axum::http::StatusCode::SERVICE_UNAVAILABLE,
+            axum::Json(serde_json::json!({ "error": "Ollama is unreachable" })),
+        )
+            .into_response();
+    };
+
+    let models: Vec<ModelInfo> = json["models"]
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .map(|m| ModelInfo {
+                    name: m["name"].as_str().unwrap_or_default().to_string(),
+                    size: m["size"].as_u64().unwrap_or(0),
+                    modified_at: m["modified_at"].as_str().unwrap_or_default().to_string(),
+                    parameter_size: m["details"]["parameter_size"].as_str().unwrap_or_default().to_string(),
+                    quantization_level: m["details"]["quantization_level"].as_str().unwrap_or_default().to_string(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    axum::Json(models).into_response()
+}
+
+#[cfg(feature = "ssr")]
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+pub enum EmbedInput {
+    One(String),
+    Many(Vec<String>),
+}
+
+#[cfg(feature = "ssr")]
+#[derive(serde::Deserialize)]
+pub struct EmbedRequest {
+    pub model: String,
+    pub input: EmbedInput,
+}
+
+#[cfg(feature = "ssr")]
+async fn embed_handler(
+    axum::Json(payload): axum::Json<EmbedRequest>,
+) -> axum::response::Response {
+    use axum::response::IntoResponse;
+
+    let endpoint = ollama_rust::app::get_ollama_endpoint_store().lock().unwrap().clone();
+    let client = ollama_rust::app::ollama_client(&endpoint);
+    let res = client
+        .post(format!("{}/api/embed", endpoint.base_url()))
+        .json(&serde_json::json!({
+            "model": payload.model,
+            "input": match payload.input {
+                EmbedInput::One(s) => serde_json::Value::String(s),
+                EmbedInput::Many(v) => serde_json::Value::from(v),
+            },
+        }))
+        .send()
+        .await;
+
+    match res {
+        Ok(response) => match response.json::<serde_json::Value>().await {
+            Ok(json) => axum::Json(serde_json::json!({
+                "embeddings": json["embeddings"].clone(),
+            }))
+            .into_response(),
+            Err(_) => (
+                axum::http::StatusCode::BAD_GATEWAY,
+                axum::Json(serde_json::json!({ "error": "Ollama returned an invalid embedding response" })),
+            )
+                .into_response(),
+        },
+        Err(_) => (
+            axum::http::StatusCode::SERVICE_UNAVAILABLE,
+            axum::Json(serde_json::json!({ "error": "Ollama is unreachable" })),
+        )
+            .into_response(),
+    }
+}
+
+#[cfg(feature = "ssr")]
+const EVENTS_LONG_POLL_TIMEOUT_SECS: u64 = 50;
+
+#[cfg(feature = "ssr")]
+const EVENTS_LONG_POLL_INTERVAL_MS: u64 = 250;
+
+#[cfg(feature = "ssr")]
+#[derive(serde::Deserialize, Default)]
+pub struct EventsRequest {
+    #[serde(default)]
+    pub models: Vec<String>,
+    #[serde(default)]
+    pub last_id: u64,
+}
+
+/// Long-polls the pull-progress event bus: blocks until a matching event
+/// shows up or `EVENTS_LONG_POLL_TIMEOUT_SECS` elapses, whichever is first,
+/// so the frontend never has to hammer this on a fixed interval.
+#[cfg(feature = "ssr")]
+async fn events_handler(axum::Json(payload): axum::Json<EventsRequest>) -> axum::Json<serde_json::Value> {
+    use ollama_rust::app::progress_events_since;
+
+    let deadline = tokio::time::Instant::now() + tokio::time::Duration::from_secs(EVENTS_LONG_POLL_TIMEOUT_SECS);
+
+    loop {
+        let events = progress_events_since(payload.last_id, &payload.models);
+        if !events.is_empty() {
+            let last_id = events.iter().map(|e| e.id).max().unwrap_or(payload.last_id);
+            return axum::Json(serde_json::json!({ "events": events, "last_id": last_id }));
+        }
+
+        if tokio::time::Instant::now() >= deadline {
+            return axum::Json(serde_json::json!({ "events": [], "last_id": payload.last_id }));
+        }
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(EVENTS_LONG_POLL_INTERVAL_MS)).await;
+    }
+}
+
 #[cfg(not(feature = "ssr"))]
 pub fn main() {}